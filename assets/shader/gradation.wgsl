@@ -27,6 +27,9 @@ struct VertexOutput {
 @group(0) @binding(0)
 var<uniform> camera: CameraUniform;
 
+// 고스트 파이프라인은 반투명하게 덮어쓴다
+override alpha: f32 = 1.0;
+
 @vertex
 fn vs_main(
     model: VertexInput,
@@ -74,5 +77,5 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let centered_coords = in.ndc_coords * vec2(0.5, -0.5) + vec2(0.5);
     let angle = atan2(centered_coords.y - 0.5, centered_coords.x - 0.5) / (2.0 * 3.14159265359) + 0.5;
     let color = hue_to_rgb(angle);
-    return vec4(color, 1.0);
+    return vec4(color, alpha);
 }
\ No newline at end of file
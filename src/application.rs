@@ -1,12 +1,12 @@
 use crate::game_state::GameState;
 use crate::renderer::*;
+use crate::resources::{Action, ContextAction, KeyMap};
 use instant::Instant;
 use wgpu::SurfaceError;
 use winit::application::ApplicationHandler;
 use winit::dpi::PhysicalSize;
 use winit::event::*;
 use winit::event_loop::{ActiveEventLoop, EventLoop};
-use winit::keyboard::{KeyCode, PhysicalKey};
 use winit::window::WindowId;
 
 pub struct Application {
@@ -14,6 +14,9 @@ pub struct Application {
     render_context: RenderContextType,
     screen_size: PhysicalSize<u32>,
     prev_time: Instant,
+    /// timeline range last sent to the page
+    #[cfg(target_arch = "wasm32")]
+    timeline_shown: Option<(u64, u64, u64)>,
 }
 
 impl ApplicationHandler<RenderContext> for Application {
@@ -23,7 +26,12 @@ impl ApplicationHandler<RenderContext> for Application {
         }
     }
 
-    fn user_event(&mut self, _event_loop: &ActiveEventLoop, graphics: RenderContext) {
+    fn user_event(&mut self, _event_loop: &ActiveEventLoop, mut graphics: RenderContext) {
+        #[cfg(not(target_arch = "wasm32"))]
+        load_fallback_font(&mut graphics.font_manager);
+        self.game_state
+            .set_screen_size(graphics.window.inner_size());
+        graphics.update_screen_buffer(self.game_state.screen_aspect_ratio());
         self.render_context = RenderContextType::Graphics(graphics);
         //이게 맞아?
         //여기서 세팅되고 redraw_request 가 호출이 안되서 생기는 문제인데
@@ -39,26 +47,49 @@ impl ApplicationHandler<RenderContext> for Application {
         event: WindowEvent,
     ) {
         match event {
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.game_state.handle_modifiers(modifiers.state())
+            }
+            WindowEvent::Focused(false) => self.game_state.handle_focus_lost(),
             WindowEvent::KeyboardInput { event, .. } => {
-                if event.state != ElementState::Pressed {
-                    return;
-                }
-                match event.physical_key {
-                    PhysicalKey::Code(KeyCode::KeyW) => {
-                        self.game_state.start_auto_playing(0.1);
-                    }
-                    PhysicalKey::Code(KeyCode::KeyQ) => {
-                        self.game_state.restart();
-                    }
-                    _ => {}
+                if let Some(action) = self.game_state.handle_keyboard_input(&event) {
+                    self.perform(action);
                 }
             }
 
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } if self.game_state.is_stamping() => {
+                self.game_state.place_stamp();
+            }
             WindowEvent::CursorMoved { .. }
             | WindowEvent::MouseWheel { .. }
             | WindowEvent::MouseInput { .. } => {
                 self.game_state.handle_mouse_input(event);
             }
+            WindowEvent::Touch(touch) => self.game_state.handle_touch(touch),
+            #[cfg(not(target_arch = "wasm32"))]
+            WindowEvent::DroppedFile(path)
+                if path
+                    .extension()
+                    .map_or(false, |extension| extension == "life") =>
+            {
+                load_session_file(&mut self.game_state, &path);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            WindowEvent::DroppedFile(path) => {
+                let seeded = std::fs::read(&path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|bytes| {
+                        self.game_state
+                            .seed_from_image(&bytes, &crate::pattern::ImageSeedOptions::default())
+                    });
+                if let Err(e) = seeded {
+                    log::warn!("failed to seed from {:?} : {}", path, e);
+                }
+            }
             WindowEvent::CloseRequested => {
                 event_loop.exit();
             }
@@ -89,6 +120,63 @@ impl Application {
         let mut gs = GameState::default();
         gs.init();
 
+        // cargo run -- --pattern "Gosper glider gun"
+        // cargo run -- --soup 1234 --density 0.4 --symmetry D8
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(name) = arg_value("--pattern") {
+                if let Err(e) = gs.select_pattern(&name) {
+                    log::warn!("{}", e);
+                }
+            }
+            // cargo run -- --apgcode xq4_153
+            if let Some(code) = arg_value("--apgcode") {
+                if let Err(e) = gs.spawn_apgcode(&code) {
+                    log::warn!("{}", e);
+                }
+            }
+            if let Some(seed) = arg_value("--soup") {
+                match soup_options_from_args(&seed) {
+                    Ok(options) => gs.reset_with_soup(options),
+                    Err(e) => log::warn!("{}", e),
+                }
+            }
+            if let Err(e) = auto_stop_from_args(&mut gs) {
+                log::warn!("{}", e);
+            }
+            if let Err(e) = rule_from_args(&mut gs) {
+                log::warn!("{}", e);
+            }
+            if let Some(path) = arg_value("--session") {
+                load_session_file(&mut gs, std::path::Path::new(&path));
+            }
+            // cargo run -- --keys my_keys.cfg
+            let key_map_path =
+                arg_value("--keys").unwrap_or(crate::configs::KEY_MAP_FILE.to_string());
+            if std::path::Path::new(&key_map_path).exists() {
+                let key_map = std::fs::read_to_string(&key_map_path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|text| KeyMap::parse(&text));
+                match key_map {
+                    Ok(key_map) => gs.set_key_map(key_map),
+                    Err(e) => log::warn!("failed to load keys from {} : {:#}", key_map_path, e),
+                }
+            }
+            if let Err(e) = speed_from_args(&mut gs) {
+                log::warn!("{}", e);
+            }
+            // cargo run -- --pattern "R-pentomino" --jump 1103
+            if let Some(generation) = arg_value("--jump") {
+                let jumped = generation
+                    .parse()
+                    .map_err(anyhow::Error::from)
+                    .and_then(|generation| gs.jump_to_generation(generation));
+                if let Err(e) = jumped {
+                    log::warn!("{}", e);
+                }
+            }
+        }
+
         Self {
             game_state: gs,
             render_context: RenderContextType::Builder(RenderContextBuilder::new(
@@ -96,6 +184,8 @@ impl Application {
             )),
             prev_time: Instant::now(),
             screen_size: PhysicalSize::default(),
+            #[cfg(target_arch = "wasm32")]
+            timeline_shown: None,
         }
     }
 
@@ -108,6 +198,9 @@ impl Application {
     fn update(&mut self) {
         let dt = self.update_delta_time();
         self.game_state.update(dt);
+        if let Some((action, cell)) = self.game_state.take_context_action() {
+            self.perform_context_action(action, cell);
+        }
 
         #[cfg(target_arch = "wasm32")]
         self.check_wasm_input();
@@ -128,25 +221,223 @@ impl Application {
 
         let instances = self.game_state.get_text_data();
         render_context.update_text_instance(instances);
+
+        let lines = self.game_state.get_overlay_lines();
+        render_context.update_line_strips(lines);
         render_context.render()
     }
 
     fn resize(&mut self, size: PhysicalSize<u32>) {
-        let RenderContextType::Graphics(_) = &mut self.render_context else {
+        self.game_state.set_screen_size(size);
+        let RenderContextType::Graphics(render_context) = &mut self.render_context else {
             log::info!("resize called but graphics not initialized");
             self.screen_size = size;
             return;
         };
+        render_context.update_screen_buffer(self.game_state.screen_aspect_ratio());
         //todo 이거 뭔가 버그 있는거 같음
         //wasm 에서 계속 크기가 2배로 커지네
         // render_context.resize(size);
     }
 
+    /// Runs what a key is bound to. Camera and cell cursor keys are read by systems instead.
+    fn perform(&mut self, action: Action) {
+        match action {
+            Action::Play => self.game_state.start_auto_playing(),
+            Action::Pause => self.game_state.stop(),
+            Action::TogglePlay => self.game_state.toggle_playing(),
+            Action::Step => self.game_state.next(1),
+            Action::StepMany => self.game_state.next(10),
+            Action::Slower => self.game_state.scale_speed(false),
+            Action::Faster => self.game_state.scale_speed(true),
+            Action::Reset => self.game_state.restart(),
+            Action::Undo => self.game_state.undo(),
+            Action::Redo => self.game_state.redo(),
+            Action::Copy => self.copy_selection(false),
+            Action::Cut => self.copy_selection(true),
+            Action::Paste => {
+                if let Err(e) = self.game_state.paste_clipboard() {
+                    log::warn!("{}", e);
+                }
+            }
+            Action::FillRandom => self.game_state.fill_selection_random(),
+            Action::Invert => self.game_state.invert_selection(),
+            Action::ClearSelected => self.game_state.clear_selected_cells(),
+            Action::ScrubBack => self.game_state.scrub(-1),
+            Action::ScrubBackMany => self.game_state.scrub(-10),
+            Action::ScrubForward => self.game_state.scrub(1),
+            Action::ScrubForwardMany => self.game_state.scrub(10),
+            Action::SelectTool => self.game_state.toggle_selection_tool(),
+            Action::Edit => self.game_state.toggle_edit(),
+            Action::DrawTool(tool) => self.game_state.select_tool(tool),
+            Action::MirrorMode => self.game_state.cycle_mirror_mode(),
+            Action::MirrorCenter => self.game_state.set_mirror_center(None),
+            Action::Stamp => self.game_state.toggle_stamp(),
+            Action::Cancel if self.game_state.is_stamping() => self.game_state.toggle_stamp(),
+            Action::Cancel => self.game_state.clear_selection(),
+            Action::RotateLeft
+            | Action::RotateRight
+            | Action::FlipHorizontal
+            | Action::FlipVertical => {
+                if let Some(stamp) = self.game_state.stamp_mut() {
+                    match action {
+                        Action::RotateLeft => stamp.rotate(false),
+                        Action::RotateRight => stamp.rotate(true),
+                        Action::FlipHorizontal => stamp.flip_horizontal(),
+                        _ => stamp.flip_vertical(),
+                    }
+                }
+            }
+            Action::Soup => self.random_soup(),
+            Action::Census => {
+                for entry in self.game_state.census() {
+                    log::info!("{}", entry);
+                }
+            }
+            Action::AutoStop => self.toggle_auto_stop(),
+            Action::NextPattern => self.cycle_pattern(true),
+            Action::PreviousPattern => self.cycle_pattern(false),
+            #[cfg(not(target_arch = "wasm32"))]
+            Action::Snapshot => self.save_snapshot(),
+            #[cfg(not(target_arch = "wasm32"))]
+            Action::SaveSession => {
+                let text = self.game_state.session().encode();
+                save_file("session", "life", Ok(text.into_bytes()));
+            }
+            Action::Graph => self.game_state.toggle_graph(),
+            #[cfg(not(target_arch = "wasm32"))]
+            Action::SaveStatistics => self.save_statistics(),
+            #[cfg(not(target_arch = "wasm32"))]
+            Action::RecordGif => self.save_animation(crate::pattern::AnimationFormat::Gif),
+            #[cfg(not(target_arch = "wasm32"))]
+            Action::RecordApng => self.save_animation(crate::pattern::AnimationFormat::Apng),
+            // 웹에서는 파일 저장을 페이지 버튼이 맡는다
+            #[cfg(target_arch = "wasm32")]
+            Action::Snapshot
+            | Action::SaveSession
+            | Action::SaveStatistics
+            | Action::RecordGif
+            | Action::RecordApng => {}
+            Action::Apgcode => match self.game_state.apgcode() {
+                Some(code) => log::info!("apgcode : {}", code),
+                None => log::info!("the cells are not a single known object"),
+            },
+            Action::PanLeft
+            | Action::PanRight
+            | Action::PanUp
+            | Action::PanDown
+            | Action::ZoomIn
+            | Action::ZoomOut
+            | Action::CursorLeft
+            | Action::CursorRight
+            | Action::CursorUp
+            | Action::CursorDown
+            | Action::ToggleCell => {}
+        }
+    }
+
+    /// Runs what was picked from the long-press menu opened at `cell`.
+    fn perform_context_action(&mut self, action: ContextAction, cell: [i32; 2]) {
+        match action {
+            ContextAction::Copy | ContextAction::Clear
+                if !self.game_state.select_object_at(cell) =>
+            {
+                log::info!("nothing at ({}, {})", cell[0], cell[1]);
+            }
+            ContextAction::Copy => self.copy_selection(false),
+            ContextAction::Paste => {
+                if let Err(e) = self.game_state.paste_at(cell) {
+                    log::warn!("{}", e);
+                }
+            }
+            ContextAction::Stamp => self.game_state.stamp_at(cell),
+            ContextAction::Clear => self.game_state.clear_selected_cells(),
+        }
+    }
+
+    /// Copies, or cuts, the selection as rle to the system clipboard as well as the internal one.
+    fn copy_selection(&mut self, cut: bool) {
+        let rle = if cut {
+            self.game_state.cut_selection()
+        } else {
+            self.game_state.copy_selection()
+        };
+        let Some(rle) = rle else {
+            log::info!("no live cells selected");
+            return;
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            log::info!("copied\n{}", rle);
+            if let Err(e) = copy_to_system_clipboard(&rle) {
+                log::warn!("system clipboard : {}", e);
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        crate::js_binding::copy_selection(&rle);
+    }
+
+    /// New soup from a fresh seed, logged so it can be grown again.
+    fn random_soup(&mut self) {
+        let options = crate::pattern::SoupOptions {
+            seed: rand::random(),
+            ..Default::default()
+        };
+        log::info!("soup seed : {}", options.seed);
+        self.game_state.reset_with_soup(options);
+    }
+
+    fn toggle_auto_stop(&mut self) {
+        let mut auto_stop = self.game_state.auto_stop_mut();
+        let enabled = auto_stop.on_extinction
+            || auto_stop.max_period.is_some()
+            || auto_stop.generation_limit.is_some();
+        *auto_stop = if enabled {
+            crate::resources::AutoStop::default()
+        } else {
+            crate::resources::AutoStop::settled()
+        };
+        log::info!("auto stop : {}", if enabled { "off" } else { "on" });
+    }
+
+    fn cycle_pattern(&mut self, forward: bool) {
+        self.game_state.cycle_pattern(forward);
+        let entry = self.game_state.library_entry();
+        log::info!("pattern : {} ({})", entry.name, entry.category.name());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_snapshot(&self) {
+        let options = crate::pattern::SnapshotOptions::default();
+        let bytes = self
+            .game_state
+            .snapshot_png(self.game_state.selection_region(), &options);
+        save_file("snapshot", "png", bytes);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_statistics(&self) {
+        let statistics = self.game_state.statistics();
+        log_statistics_summary(&statistics);
+        save_file("statistics", "csv", Ok(statistics.to_csv().into_bytes()));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_animation(&self, format: crate::pattern::AnimationFormat) {
+        let options = crate::pattern::AnimationOptions {
+            format,
+            ..Default::default()
+        };
+        let bytes = self.game_state.record_animation(&options);
+        save_file("animation", format.extension(), bytes);
+    }
+
     #[cfg(target_arch = "wasm32")]
     pub fn check_wasm_input(&mut self) {
         use crate::js_binding::JS_BINDING;
         if JS_BINDING.get_state(0) {
-            self.start_auto_playing(0.1);
+            self.start_auto_playing();
         }
 
         if JS_BINDING.get_state(1) {
@@ -161,12 +452,181 @@ impl Application {
         if JS_BINDING.get_state(3) {
             self.step_game();
         }
+
+        if JS_BINDING.get_state(4) {
+            self.copy_apgcode();
+        }
+
+        if JS_BINDING.get_state(5) {
+            self.download_snapshot();
+        }
+
+        if JS_BINDING.get_state(7) {
+            let report = self
+                .game_state
+                .census()
+                .iter()
+                .map(|entry| entry.to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+            crate::js_binding::show_census(&report);
+        }
+
+        if JS_BINDING.get_state(9) {
+            self.game_state.toggle_graph();
+        }
+
+        if JS_BINDING.get_state(8) {
+            let statistics = self.game_state.statistics();
+            log_statistics_summary(&statistics);
+            let csv = statistics.to_csv();
+            crate::js_binding::download_file("statistics.csv", csv.as_bytes());
+        }
+
+        if JS_BINDING.get_state(6) {
+            let text = self.game_state.session().encode();
+            crate::js_binding::download_file("session.life", text.as_bytes());
+        }
+
+        if JS_BINDING.get_state(10) {
+            self.game_state.toggle_selection_tool();
+        }
+
+        if JS_BINDING.get_state(11) {
+            self.copy_selection(false);
+        }
+
+        if JS_BINDING.get_state(12) {
+            self.copy_selection(true);
+        }
+
+        if JS_BINDING.get_state(13) {
+            self.game_state.clear_selected_cells();
+        }
+
+        if JS_BINDING.get_state(14) {
+            self.game_state.fill_selection_random();
+        }
+
+        if JS_BINDING.get_state(15) {
+            self.game_state.invert_selection();
+        }
+
+        if JS_BINDING.get_state(16) {
+            self.game_state.undo();
+        }
+
+        if JS_BINDING.get_state(17) {
+            self.game_state.redo();
+        }
+
+        if JS_BINDING.get_state(18) {
+            self.game_state.toggle_edit();
+        }
+
+        if let Some(tool) = JS_BINDING.take_tool() {
+            self.game_state.select_tool(tool);
+        }
+
+        // 렌더러가 준비될 때까지는 폰트를 꺼내지 않고 둔다
+        if let RenderContextType::Graphics(graphics) = &mut self.render_context {
+            if let Some(bytes) = JS_BINDING.take_font() {
+                if let Err(e) = graphics.font_manager.add_font(&bytes) {
+                    log::warn!("failed to load font : {:#}", e);
+                }
+            }
+        }
+
+        if let Some(text) = JS_BINDING.take_key_map() {
+            match KeyMap::parse(&text) {
+                Ok(key_map) => self.game_state.set_key_map(key_map),
+                Err(e) => log::warn!("{:#}", e),
+            }
+        }
+
+        if let Some((mode, center)) = JS_BINDING.take_mirror() {
+            self.game_state.set_mirror_mode(mode);
+            self.game_state.set_mirror_center(Some(center));
+        }
+
+        if let Some(speed) = JS_BINDING.take_speed() {
+            self.game_state.set_speed(speed);
+        }
+
+        if let Some(generations) = JS_BINDING.take_steps() {
+            self.game_state.next(generations);
+        }
+
+        if let Some(generation) = JS_BINDING.take_jump() {
+            if let Err(e) = self.game_state.jump_to_generation(generation) {
+                log::warn!("{}", e);
+            }
+        }
+
+        let timeline = self
+            .game_state
+            .timeline_range()
+            .map(|(first, last)| (first, last, self.game_state.generation()));
+        if timeline != self.timeline_shown {
+            if let Some((first, last, current)) = timeline {
+                // JS 숫자는 2^53 까지 정확하니 u32 로 자르지 않는다
+                crate::js_binding::update_timeline(first as f64, last as f64, current as f64);
+            }
+            self.timeline_shown = timeline;
+        }
+
+        if let Some(rle) = JS_BINDING.take_paste() {
+            self.game_state.set_clipboard(rle);
+            if let Err(e) = self.game_state.paste_clipboard() {
+                log::warn!("{}", e);
+            }
+        }
+
+        if let Some(text) = JS_BINDING.take_session() {
+            match crate::session::Session::decode(&text) {
+                Ok(session) => self.game_state.load_session(&session),
+                Err(e) => log::warn!("{}", e),
+            }
+        }
+
+        if let Some(format) = JS_BINDING.take_animation_format() {
+            self.download_animation(format);
+        }
+
+        if let Some((bytes, options)) = JS_BINDING.take_image() {
+            if let Err(e) = self.game_state.seed_from_image(&bytes, &options) {
+                log::warn!("{}", e);
+            }
+        }
+
+        if let Some(name) = JS_BINDING.take_pattern_name() {
+            if let Err(e) = self.game_state.select_pattern(&name) {
+                log::warn!("{}", e);
+            }
+        }
+
+        if let Some(options) = JS_BINDING.take_soup() {
+            self.game_state.reset_with_soup(options);
+        }
+
+        if let Some((on_extinction, max_period, generation_limit)) = JS_BINDING.take_auto_stop() {
+            let mut auto_stop = self.game_state.auto_stop_mut();
+            auto_stop.on_extinction = on_extinction;
+            auto_stop.max_period = max_period;
+            auto_stop.generation_limit = generation_limit;
+        }
+
+        if let Some(code) = JS_BINDING.take_apgcode() {
+            if let Err(e) = self.game_state.spawn_apgcode(&code) {
+                log::warn!("{}", e);
+            }
+        }
         JS_BINDING.reset();
     }
 
     #[cfg(target_arch = "wasm32")]
-    pub fn start_auto_playing(&mut self, tick: f32) {
-        self.game_state.start_auto_playing(tick);
+    pub fn start_auto_playing(&mut self) {
+        self.game_state.start_auto_playing();
     }
     #[cfg(target_arch = "wasm32")]
     pub fn reset_game(&mut self) {
@@ -180,6 +640,202 @@ impl Application {
 
     #[cfg(target_arch = "wasm32")]
     pub fn step_game(&mut self) {
-        self.game_state.next();
+        self.game_state.next(1);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn download_snapshot(&mut self) {
+        let options = crate::pattern::SnapshotOptions::default();
+        match self
+            .game_state
+            .snapshot_png(self.game_state.selection_region(), &options)
+        {
+            Ok(bytes) => crate::js_binding::download_file("snapshot.png", &bytes),
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn download_animation(&mut self, format: crate::pattern::AnimationFormat) {
+        let options = crate::pattern::AnimationOptions {
+            format,
+            ..Default::default()
+        };
+        match self.game_state.record_animation(&options) {
+            Ok(bytes) => {
+                let name = format!("animation.{}", format.extension());
+                crate::js_binding::download_file(&name, &bytes);
+            }
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn copy_apgcode(&mut self) {
+        match self.game_state.apgcode() {
+            Some(code) => crate::js_binding::copy_to_clipboard(&code),
+            None => log::info!("the cells are not a single known object"),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_file(name: &str, extension: &str, bytes: anyhow::Result<Vec<u8>>) {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let path = format!("{}-{}.{}", name, timestamp, extension);
+    match bytes.and_then(|bytes| Ok(std::fs::write(&path, bytes)?)) {
+        Ok(_) => log::info!("saved {}", path),
+        Err(e) => log::warn!("failed to save {} : {}", path, e),
+    }
+}
+
+/// Hands `text` to the platform's clipboard tool, since there is no clipboard crate here.
+#[cfg(not(target_arch = "wasm32"))]
+fn copy_to_system_clipboard(text: &str) -> anyhow::Result<()> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let tools: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else if cfg!(target_os = "windows") {
+        &[("clip", &[])]
+    } else {
+        &[
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ]
+    };
+    for (program, args) in tools {
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+    anyhow::bail!("no clipboard tool found")
+}
+
+fn log_statistics_summary(statistics: &crate::resources::Statistics) {
+    for metric in crate::resources::Metric::ALL {
+        if let Some(summary) = statistics.summary(0..=u64::MAX, metric) {
+            log::info!(
+                "{} min {} max {} mean {:.3}",
+                metric,
+                summary.min,
+                summary.max,
+                summary.mean
+            );
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn arg_value(name: &str) -> Option<String> {
+    std::env::args().skip_while(|arg| arg != name).nth(1)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn soup_options_from_args(seed: &str) -> anyhow::Result<crate::pattern::SoupOptions> {
+    let mut options = crate::pattern::SoupOptions {
+        seed: crate::pattern::parse_seed(seed),
+        ..Default::default()
+    };
+    if let Some(density) = arg_value("--density") {
+        options.density = density.parse()?;
+    }
+    if let Some(symmetry) = arg_value("--symmetry") {
+        options.symmetry = symmetry.parse()?;
+    }
+    Ok(options)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn rule_from_args(game_state: &mut GameState) -> anyhow::Result<()> {
+    if arg_value("--rule").is_none() && arg_value("--boundary").is_none() {
+        return Ok(());
+    }
+    let rule = match arg_value("--rule") {
+        Some(rule) => rule.parse()?,
+        None => crate::pattern::Rule::default(),
+    };
+    let boundary = match arg_value("--boundary") {
+        Some(boundary) => boundary.parse()?,
+        None => crate::pattern::Boundary::default(),
+    };
+    game_state.set_rule(rule, boundary);
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_session_file(game_state: &mut GameState, path: &std::path::Path) {
+    let session = std::fs::read_to_string(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|text| crate::session::Session::decode(&text));
+    match session {
+        Ok(session) => game_state.load_session(&session),
+        Err(e) => log::warn!("failed to load session {:?} : {}", path, e),
+    }
+}
+
+/// `--font path`, or else the first of `FALLBACK_FONT_FILES` that exists.
+#[cfg(not(target_arch = "wasm32"))]
+fn load_fallback_font(font_manager: &mut crate::renderer::FontManager) {
+    let path = arg_value("--font").or_else(|| {
+        crate::configs::FALLBACK_FONT_FILES
+            .iter()
+            .find(|path| std::path::Path::new(path).exists())
+            .map(|path| path.to_string())
+    });
+    let Some(path) = path else {
+        return;
+    };
+    let loaded = std::fs::read(&path)
+        .map_err(anyhow::Error::from)
+        .and_then(|bytes| font_manager.add_font(&bytes));
+    if let Err(e) = loaded {
+        log::warn!("failed to load font {} : {:#}", path, e);
+    }
+}
+
+/// `--speed 30` plays 30 generations a second, `--per-frame 4` four on every frame.
+#[cfg(not(target_arch = "wasm32"))]
+fn speed_from_args(game_state: &mut GameState) -> anyhow::Result<()> {
+    if let Some(rate) = arg_value("--speed") {
+        let rate: f32 = rate.parse()?;
+        game_state.set_speed(crate::resources::Speed::PerSecond(rate).clamped());
+    }
+    if let Some(generations) = arg_value("--per-frame") {
+        let generations: u32 = generations.parse()?;
+        game_state.set_speed(crate::resources::Speed::PerFrame(generations).clamped());
+    }
+    Ok(())
+}
+
+/// Every condition starts off. `--stop-empty true` stops on extinction,
+/// and `--stop-period 0` and `--stop-at 0` turn their condition off.
+#[cfg(not(target_arch = "wasm32"))]
+fn auto_stop_from_args(game_state: &mut GameState) -> anyhow::Result<()> {
+    let mut auto_stop = game_state.auto_stop_mut();
+    if let Some(on_extinction) = arg_value("--stop-empty") {
+        auto_stop.on_extinction = on_extinction.parse()?;
+    }
+    if let Some(period) = arg_value("--stop-period") {
+        auto_stop.max_period = Some(period.parse()?).filter(|&period| period > 0);
+    }
+    if let Some(generation) = arg_value("--stop-at") {
+        auto_stop.generation_limit = Some(generation.parse()?).filter(|&generation| generation > 0);
     }
+    Ok(())
 }
@@ -1,3 +1,58 @@
 pub const SCREEN_SIZE: [u32; 2] = [1024, 768];
 pub const GRID_SIZE_HALF: [usize; 2] = [37, 28];
-pub const LIFE_TICK: f32 = 0.1;
+pub const GRID_SIZE: [usize; 2] = [GRID_SIZE_HALF[0] * 2 + 1, GRID_SIZE_HALF[1] * 2 + 1];
+pub const GENERATIONS_PER_SECOND: f32 = 10.0;
+pub const MIN_GENERATIONS_PER_SECOND: f32 = 0.25;
+pub const MAX_GENERATIONS_PER_SECOND: f32 = 10_000.0;
+/// generations stepped in one frame at most, however far the clock fell behind
+pub const MAX_STEPS_PER_FRAME: u32 = 1000;
+pub const MAX_OBJECT_PERIOD: u32 = 64;
+/// generations remembered when looking for cycles, the longest period that can be found
+pub const CYCLE_HISTORY: usize = 512;
+/// default bound for pausing once the grid turns periodic
+pub const AUTO_STOP_MAX_PERIOD: u32 = 2;
+/// generations of statistics kept before the oldest are dropped
+pub const STATISTICS_CAPACITY: usize = 100_000;
+/// memory the undo history may use before the oldest changes are dropped
+pub const HISTORY_BYTES: usize = 8 << 20;
+/// generations between full copies of the grid on the timeline
+pub const KEYFRAME_INTERVAL: usize = 64;
+/// memory the timeline may use before the oldest generations are dropped
+pub const TIMELINE_BYTES: usize = 16 << 20;
+/// generations a jump may step past the current one, so a typo can't freeze the app
+pub const MAX_JUMP_GENERATIONS: u64 = 100_000;
+/// generations shown by the population graph
+pub const GRAPH_GENERATIONS: usize = 200;
+/// `[x_min, y_min, x_max, y_max]` of the population graph in normalized device coordinates
+pub const GRAPH_RECT: [f32; 4] = [-0.95, -0.95, -0.45, -0.65];
+/// key bindings read at startup when present, see `KeyMap`
+pub const KEY_MAP_FILE: &str = "keys.cfg";
+/// screen pixels per second the camera pans as soon as a pan key is held
+pub const KEY_PAN_SPEED: f32 = 300.0;
+/// how much faster panning gets for every second the key stays down
+pub const KEY_PAN_ACCELERATION: f32 = 2.0;
+pub const KEY_PAN_MAX_SPEED: f32 = 1500.0;
+/// seconds a cursor key is held before the cursor keeps moving on its own
+pub const CURSOR_REPEAT_DELAY: f32 = 0.3;
+/// cells per second the cursor moves while its key stays down
+pub const CURSOR_REPEAT_RATE: f32 = 15.0;
+/// camera height per second while a zoom key is held
+pub const KEY_ZOOM_SPEED: f32 = 30.0;
+/// screen pixels a finger may wander before a touch counts as a drag
+pub const TOUCH_SLOP: f32 = 10.0;
+/// seconds a finger stays still before a touch counts as a long press
+pub const LONG_PRESS_SECONDS: f32 = 0.5;
+/// size of a long-press menu item in normalized device coordinates
+pub const CONTEXT_MENU_WIDTH: f32 = 0.3;
+pub const CONTEXT_MENU_ITEM_HEIGHT: f32 = 0.09;
+/// camera height per screen pixel the fingers of a pinch move apart or together
+pub const PINCH_ZOOM_SPEED: f32 = 0.05;
+/// system fonts with Hangul, the first one found is used for what the bundled font lacks
+#[allow(unused)]
+pub const FALLBACK_FONT_FILES: [&str; 5] = [
+    "C:\\Windows\\Fonts\\malgun.ttf",
+    "/System/Library/Fonts/AppleSDGothicNeo.ttc",
+    "/usr/share/fonts/truetype/nanum/NanumGothic.ttf",
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+];
@@ -1,6 +1,10 @@
+use std::collections::HashSet;
+
 use crate::components::*;
 use crate::configs;
-use specs::{Builder, World, WorldExt};
+use crate::pattern::{Boundary, Grid, Pattern, Rule};
+use crate::resources::{CycleDetector, History, Statistics, Timeline};
+use specs::{Builder, World, WorldExt, WriteStorage};
 
 pub fn cell_grid(world: &mut World) {
     let x_min = -(configs::GRID_SIZE_HALF[0] as i32);
@@ -36,6 +40,53 @@ pub fn cell_grid(world: &mut World) {
     world.insert(entity_map);
 }
 
+/// `Cell::index` of the cell at a world position, `None` outside the grid.
+pub fn cell_index([x, y]: [i32; 2]) -> Option<u32> {
+    let half = [
+        configs::GRID_SIZE_HALF[0] as i32,
+        configs::GRID_SIZE_HALF[1] as i32,
+    ];
+    if x.abs() > half[0] || y.abs() > half[1] {
+        return None;
+    }
+    Some(((x + half[0]) * configs::GRID_SIZE[1] as i32 + y + half[1]) as u32)
+}
+
+/// Applies `edit` to the cells whose `Cell::index` is in `targets`, and records the flips
+/// as an edit by hand at `generation`. Open edits are merged while they stay open, like a stroke.
+pub fn apply_cell_edit(
+    cells: &mut WriteStorage<Cell>,
+    targets: &HashSet<u32>,
+    mut edit: impl FnMut(bool) -> bool,
+    (detector, timeline, history, statistics): (
+        &mut CycleDetector,
+        &mut Timeline,
+        &mut History,
+        &mut Statistics,
+    ),
+    generation: u64,
+    open: bool,
+) {
+    use specs::Join;
+    let mut flips = vec![];
+    for cell in cells.join() {
+        if targets.contains(&cell.index) {
+            let alive = edit(cell.alive);
+            if alive != cell.alive {
+                cell.alive = alive;
+                flips.push(cell.index);
+            }
+        }
+    }
+    // 손으로 바꾼 칸은 스텝이 아니므로 주기 기록을 버린다
+    if !flips.is_empty() {
+        detector.reset();
+        timeline.record_edit(generation, &flips);
+        statistics.record_edit(&flips);
+        history.record_edit(flips, [generation; 2], open);
+    }
+}
+
 pub fn set_cells_alive_at_positions(world: &mut World, positions_to_set_alive: Vec<[f32; 2]>) {
     use specs::Join;
 
@@ -65,3 +116,56 @@ pub fn set_cells_alive_at_positions(world: &mut World, positions_to_set_alive: V
         }
     }
 }
+
+pub fn read_grid(world: &World) -> Grid {
+    use specs::Join;
+
+    let cells = world.read_storage::<Cell>();
+    let mut grid = Grid::default();
+    grid.set_rule(
+        *world.read_resource::<Rule>(),
+        *world.read_resource::<Boundary>(),
+    );
+    for cell in cells.join() {
+        grid.set(cell.index as usize, cell.alive);
+    }
+    grid
+}
+
+pub fn live_cell_pattern(world: &World) -> Pattern {
+    read_grid(world).to_pattern()
+}
+
+/// World cell positions of `pattern` with its bounding box centered on `center`.
+pub fn pattern_world_cells(pattern: &Pattern, center: [i32; 2]) -> Vec<[i32; 2]> {
+    let Some(bbox) = pattern.bounding_box() else {
+        return vec![];
+    };
+    let offset = [
+        center[0] - (bbox[0] + bbox[2]) / 2,
+        center[1] + (bbox[1] + bbox[3]) / 2,
+    ];
+    pattern
+        .cells()
+        .iter()
+        .map(|&[x, y]| [x + offset[0], offset[1] - y])
+        .collect()
+}
+
+pub fn set_pattern_alive(world: &mut World, pattern: &Pattern, center: [i32; 2]) {
+    let positions = pattern_world_cells(pattern, center)
+        .into_iter()
+        .map(|[x, y]| [x as f32, y as f32])
+        .collect();
+    set_cells_alive_at_positions(world, positions);
+}
+
+/// Like `set_pattern_alive`, but keeps the pattern where it is instead of centering it.
+pub fn set_pattern_alive_in_place(world: &mut World, pattern: &Pattern) {
+    let positions = pattern
+        .cells()
+        .iter()
+        .map(|&[x, y]| [x as f32, -y as f32])
+        .collect();
+    set_cells_alive_at_positions(world, positions);
+}
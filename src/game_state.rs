@@ -1,28 +1,43 @@
 use cgmath::Vector2;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use specs::{Join, World, WorldExt};
 
 use crate::entity_builder::*;
 
 use crate::components::*;
+use crate::configs;
+use crate::pattern::*;
 use crate::renderer::*;
 use crate::resources::Camera;
 use crate::resources::*;
+use crate::session::{Session, SessionPhase};
 use crate::system;
 use crate::system::UnifiedDispatcher;
 
+/// Whether the grid was set up and not started yet, or is being drawn on.
+/// How generations advance is up to `SimulationClock`.
 enum Phase {
     Ready,
     Play,
-    // Step,
-    // Edit,
+    Edit,
 }
 
 pub struct GameState {
     pub world: World,
     dispatcher: Box<dyn UnifiedDispatcher + 'static>,
     phase: Phase,
+    /// index into `LIBRARY`, seeded again on every restart
+    library_entry: usize,
+    /// when set, restart grows this soup instead of the library entry
+    soup: Option<SoupOptions>,
+    /// pattern following the mouse until it is placed
+    stamp: Option<Stamp>,
+    /// why and when the play loop last paused itself
+    last_stop: Option<(u64, StopReason)>,
+    show_graph: bool,
+    /// rle of the last copied selection
+    clipboard: Option<String>,
 }
 
 impl Default for GameState {
@@ -31,6 +46,12 @@ impl Default for GameState {
             world: World::new(),
             dispatcher: system::build(),
             phase: Phase::Ready,
+            library_entry: DEFAULT_ENTRY,
+            soup: None,
+            stamp: None,
+            last_stop: None,
+            show_graph: true,
+            clipboard: None,
         }
     }
 }
@@ -43,75 +64,137 @@ impl GameState {
 
         self.world.insert(Camera::new(1.33333));
         self.world.insert(InputHandler::default());
-        self.world.insert(StageTick::default());
+        self.world.insert(SimulationClock::default());
+        self.world.insert(Rule::default());
+        self.world.insert(Boundary::default());
+        self.world.insert(Stepped::default());
+        self.world.insert(CycleDetector::default());
+        self.world.insert(AutoStop::default());
+        self.world.insert(Statistics::default());
+        self.world.insert(Stroke::default());
+        self.world.insert(Selection::default());
+        self.world.insert(History::default());
+        self.world.insert(Timeline::default());
+        self.world.insert(DrawTool::default());
+        self.world.insert(Mirror::default());
+        self.world.insert(KeyMap::default());
+        self.world.insert(FrameTime::default());
+        self.world.insert(KeyboardPan::default());
+        self.world.insert(CellCursor::default());
+        self.world.insert(TouchGesture::default());
+        self.world.insert(ContextMenu::default());
 
         self.init_game();
+        // 처음 불러온 패턴은 되돌릴 대상이 아니다
+        let grid = read_grid(&self.world);
+        self.world.write_resource::<History>().reset(grid);
     }
 
     fn init_game(&mut self) {
-        self.phase = Phase::Ready;
-        // agent(&mut self.world);
-        cell_grid(&mut self.world);
+        let pattern = match &self.soup {
+            Some(options) => random_soup(options),
+            None => LIBRARY[self.library_entry].pattern(),
+        };
+        self.load_pattern(&pattern);
+    }
 
-        let positions_to_set_alive = vec![
-            [-5.0, 0.0],
-            [-4.0, 0.0],
-            [-3.0, 0.0],
-            [-2.0, 0.0],
-            [-1.0, 0.0],
-            [0.0, 0.0],
-            [1.0, 0.0],
-            [2.0, 0.0],
-            [3.0, 0.0],
-            [4.0, 0.0],
-            [5.0, 0.0],
-            [6.0, 0.0],
-            [-3.0, 2.0],
-            [-2.0, 2.0],
-            [-1.0, 2.0],
-            [0.0, 2.0],
-            [1.0, 2.0],
-            [2.0, 2.0],
-            [3.0, 2.0],
-            [4.0, 2.0],
-            // y = -2일 때, x = -3부터 4까지
-            [-3.0, -2.0],
-            [-2.0, -2.0],
-            [-1.0, -2.0],
-            [0.0, -2.0],
-            [1.0, -2.0],
-            [2.0, -2.0],
-            [3.0, -2.0],
-            [4.0, -2.0],
-            // y = 4일 때, x = -1부터 2까지
-            [-1.0, 4.0],
-            [0.0, 4.0],
-            [1.0, 4.0],
-            [2.0, 4.0],
-            // y = -4일 때, x = -1부터 2까지
-            [-1.0, -4.0],
-            [0.0, -4.0],
-            [1.0, -4.0],
-            [2.0, -4.0],
-        ];
-        set_cells_alive_at_positions(&mut self.world, positions_to_set_alive);
+    /// Runs the systems once per due generation, at least once per frame for input.
+    /// An auto stop ends the frame right at the generation it fired on.
+    pub fn update(&mut self, dt: f32) {
+        self.world.write_resource::<SimulationClock>().advance(dt);
+        self.world.write_resource::<FrameTime>().dt = dt;
+        loop {
+            self.dispatcher.run_now(&mut self.world);
+            self.world.maintain();
+            self.world.write_resource::<FrameTime>().dt = 0.0;
+
+            let fired = self.world.write_resource::<AutoStop>().fired.take();
+            if let Some((generation, reason)) = fired {
+                log::info!("stopped at generation {} : {}", generation, reason);
+                self.set_ready();
+                self.last_stop = Some((generation, reason));
+                break;
+            }
+            if !self.world.read_resource::<SimulationClock>().has_due() {
+                break;
+            }
+        }
+
+        let drawn = self.world.write_resource::<DrawTool>().drawn.take();
+        if let Some(drawn) = drawn {
+            self.draw(drawn);
+        }
     }
 
-    fn update_delta_time(&mut self, dt: f32) {
-        match self.phase {
-            Phase::Ready => {}
-            Phase::Play => {
-                let mut stage = self.world.write_resource::<StageTick>();
-                stage.current_spent += dt;
-            } // Phase::Step => {}
-              // Phase::Edit => {}
+    /// Back to waiting for a start, as after loading a grid. Editing goes on if it was.
+    fn set_ready(&mut self) {
+        if !matches!(self.phase, Phase::Edit) {
+            self.phase = Phase::Ready;
         }
+        self.world.write_resource::<SimulationClock>().pause();
     }
 
-    pub fn update(&mut self, dt: f32) {
-        self.update_delta_time(dt);
-        self.dispatcher.run_now(&mut self.world);
-        self.world.maintain();
+    fn set_playing(&mut self) {
+        self.phase = Phase::Play;
+        self.world.write_resource::<DrawTool>().editing = false;
+    }
+
+    pub fn is_editing(&self) -> bool {
+        matches!(self.phase, Phase::Edit)
+    }
+
+    /// Pauses and lets mouse drags use the drawing tools, or goes back to ready.
+    pub fn toggle_edit(&mut self) {
+        let editing = !self.is_editing();
+        self.phase = if editing { Phase::Edit } else { Phase::Ready };
+        self.world.write_resource::<SimulationClock>().pause();
+        let mut draw_tool = self.world.write_resource::<DrawTool>();
+        draw_tool.editing = editing;
+        draw_tool.anchor = None;
+    }
+
+    /// Picks the tool for the edit phase, entering it if needed.
+    pub fn select_tool(&mut self, tool: Tool) {
+        if !self.is_editing() {
+            self.toggle_edit();
+        }
+        let mut draw_tool = self.world.write_resource::<DrawTool>();
+        draw_tool.tool = tool;
+        draw_tool.anchor = None;
+    }
+
+    fn draw(&mut self, drawn: Drawn) {
+        let Drawn {
+            tool,
+            from,
+            to,
+            alive,
+        } = drawn;
+        let mirror = self.world.read_resource::<Mirror>();
+        let cells = match tool {
+            Tool::Pencil | Tool::Line => mirror.mirrored(&line(from, to)),
+            Tool::Rectangle => mirror.mirrored(&rectangle(from, to, false)),
+            Tool::FilledRectangle => mirror.mirrored(&rectangle(from, to, true)),
+            Tool::Ellipse => mirror.mirrored(&ellipse(from, to, false)),
+            Tool::FilledEllipse => mirror.mirrored(&ellipse(from, to, true)),
+            // 누른 칸과 같은 상태로 이어진 영역을 바꾼다. 왼쪽은 죽은 영역, 오른쪽은 산 영역
+            // 영역을 뒤집어 옮기면 다른 칸을 덮을 수 있어서 시작 칸을 대칭으로 옮겨 각각 채운다
+            Tool::FloodFill => {
+                let grid = read_grid(&self.world);
+                mirror
+                    .images(to)
+                    .into_iter()
+                    .flat_map(|seed| {
+                        flood_fill(seed, |cell| {
+                            cell_index(cell)
+                                .map_or(false, |index| grid.cells()[index as usize] != alive)
+                        })
+                    })
+                    .collect()
+            }
+        };
+        drop(mirror);
+        self.edit_cells(&cells, |_| alive);
     }
 
     pub fn handle_mouse_input(&mut self, event: winit::event::WindowEvent) -> bool {
@@ -148,6 +231,62 @@ impl GameState {
         }
     }
 
+    /// Window size in physical pixels, which cursor and touch positions are measured in.
+    pub fn set_screen_size(&mut self, size: winit::dpi::PhysicalSize<u32>) {
+        self.world
+            .write_resource::<Camera>()
+            .set_screen_size([size.width as f32, size.height as f32]);
+    }
+
+    /// Width over height of the window, the scale of the screen units text and menus are placed in.
+    pub fn screen_aspect_ratio(&self) -> f32 {
+        let [width, height] = self.world.read_resource::<Camera>().screen_size();
+        width / height
+    }
+
+    /// Hands fingers to `InterpretTouches`, except one landing while a stamp is held, which places it.
+    pub fn handle_touch(&mut self, touch: winit::event::Touch) {
+        let position = <[f32; 2]>::from(touch.location).into();
+        if touch.phase == winit::event::TouchPhase::Started && self.is_stamping() {
+            self.world
+                .write_resource::<InputHandler>()
+                .warp_cursor(position);
+            self.place_stamp();
+            return;
+        }
+        self.world
+            .write_resource::<InputHandler>()
+            .touch_input(touch.id, touch.phase, position);
+    }
+
+    /// Keeps track of held keys and returns the action bound to a key going down.
+    pub fn handle_keyboard_input(&mut self, event: &winit::event::KeyEvent) -> Option<Action> {
+        use winit::keyboard::PhysicalKey;
+        let PhysicalKey::Code(key) = event.physical_key else {
+            return None;
+        };
+        let pressed = event.state.is_pressed();
+        let mut input_handler = self.world.write_resource::<InputHandler>();
+        input_handler.key_input(pressed, key);
+        if !pressed {
+            return None;
+        }
+        let modifiers = input_handler.modifiers;
+        self.world.read_resource::<KeyMap>().action(key, modifiers)
+    }
+
+    pub fn handle_modifiers(&mut self, modifiers: winit::keyboard::ModifiersState) {
+        self.world.write_resource::<InputHandler>().modifiers = modifiers;
+    }
+
+    pub fn handle_focus_lost(&mut self) {
+        self.world.write_resource::<InputHandler>().release_keys();
+    }
+
+    pub fn set_key_map(&mut self, key_map: KeyMap) {
+        self.world.insert(key_map);
+    }
+
     pub fn get_camera_uniform(&self) -> [[f32; 4]; 4] {
         let camera = self.world.read_resource::<Camera>();
         camera.get_view_proj()
@@ -178,37 +317,933 @@ impl GameState {
                 .push(instance);
         }
 
+        // 스탬프가 없어도 빈 목록을 넣어야 이전 고스트가 지워진다
+        let ghost = self
+            .ghost_cells()
+            .into_iter()
+            .map(|[x, y]| TileAttributes {
+                uv: [0.0, 1.0, 0.0, 1.0],
+                position: [x as f32, y as f32, 0.01],
+                size: [0.85, 0.85],
+                rotation: cgmath::Rad(0.0),
+            })
+            .collect();
+        tile_instance_data_hashmap.insert("ghost".to_string(), ghost);
+
         tile_instance_data_hashmap
     }
 
+    /// Population graph of the latest generations, drawn over the grid in screen space.
+    pub fn get_overlay_lines(&self) -> Vec<LineStrip> {
+        let mut lines = vec![];
+        if let Some(outline) = self.selection_outline() {
+            lines.push(outline);
+        }
+        if let Some(preview) = self.shape_preview() {
+            lines.push(preview);
+        }
+        if let Some(cursor) = self.world.read_resource::<CellCursor>().cell {
+            let [x, y] = [cursor[0] as f32, cursor[1] as f32];
+            lines.push(self.world_strip(
+                &[
+                    [x - 0.5, y - 0.5],
+                    [x + 0.5, y - 0.5],
+                    [x + 0.5, y + 0.5],
+                    [x - 0.5, y + 0.5],
+                    [x - 0.5, y - 0.5],
+                ],
+                [0.3, 0.9, 1.0, 1.0],
+            ));
+        }
+        lines.extend(self.context_menu_outline());
+        lines.extend(self.mirror_guides());
+        if self.show_graph {
+            lines.extend(self.population_graph());
+        }
+        lines
+    }
+
+    /// Frames around the long-press menu items and the cell they act on.
+    fn context_menu_outline(&self) -> Vec<LineStrip> {
+        let menu = self.world.read_resource::<ContextMenu>();
+        let Some([x, y]) = menu.cell else {
+            return vec![];
+        };
+        let [x, y] = [x as f32, y as f32];
+        let color = [1.0, 0.85, 0.2, 1.0];
+        let mut lines = vec![self.world_strip(
+            &[
+                [x - 0.5, y - 0.5],
+                [x + 0.5, y - 0.5],
+                [x + 0.5, y + 0.5],
+                [x - 0.5, y + 0.5],
+                [x - 0.5, y - 0.5],
+            ],
+            color,
+        )];
+        lines.extend(menu.items().map(|(_, [x0, y0, x1, y1])| LineStrip {
+            points: vec![[x0, y0], [x1, y0], [x1, y1], [x0, y1], [x0, y0]],
+            color,
+        }));
+        lines
+    }
+
+    /// Selected rectangle traced along the cell edges, projected onto the screen.
+    fn selection_outline(&self) -> Option<LineStrip> {
+        let [x0, y0, x1, y1] = self.world.read_resource::<Selection>().rect?;
+        let [x0, y0, x1, y1] = [
+            x0 as f32 - 0.5,
+            y0 as f32 - 0.5,
+            x1 as f32 + 0.5,
+            y1 as f32 + 0.5,
+        ];
+        // 평면 위의 직선은 원근 투영해도 직선이라 꼭짓점만 옮기면 된다
+        Some(self.world_strip(
+            &[[x0, y0], [x1, y0], [x1, y1], [x0, y1], [x0, y0]],
+            [1.0, 0.85, 0.2, 1.0],
+        ))
+    }
+
+    /// Outline of the shape being dragged, before it is drawn.
+    fn shape_preview(&self) -> Option<LineStrip> {
+        let draw_tool = self.world.read_resource::<DrawTool>();
+        let (from, alive) = draw_tool.anchor?;
+        let to = draw_tool.cursor;
+        let [x0, x1] = [
+            from[0].min(to[0]) as f32 - 0.5,
+            from[0].max(to[0]) as f32 + 0.5,
+        ];
+        let [y0, y1] = [
+            from[1].min(to[1]) as f32 - 0.5,
+            from[1].max(to[1]) as f32 + 0.5,
+        ];
+        let points = match draw_tool.tool {
+            Tool::Pencil | Tool::FloodFill => return None,
+            Tool::Line => vec![
+                [from[0] as f32, from[1] as f32],
+                [to[0] as f32, to[1] as f32],
+            ],
+            Tool::Rectangle | Tool::FilledRectangle => {
+                vec![[x0, y0], [x1, y0], [x1, y1], [x0, y1], [x0, y0]]
+            }
+            Tool::Ellipse | Tool::FilledEllipse => (0..=48)
+                .map(|i| {
+                    let angle = i as f32 / 48.0 * std::f32::consts::TAU;
+                    [
+                        (x0 + x1) / 2.0 + (x1 - x0) / 2.0 * angle.cos(),
+                        (y0 + y1) / 2.0 + (y1 - y0) / 2.0 * angle.sin(),
+                    ]
+                })
+                .collect(),
+        };
+        let color = if alive {
+            [0.4, 1.0, 0.5, 1.0]
+        } else {
+            [1.0, 0.4, 0.4, 1.0]
+        };
+        Some(self.world_strip(&points, color))
+    }
+
+    fn mirror_guides(&self) -> Vec<LineStrip> {
+        let guides = self.world.read_resource::<Mirror>().guides();
+        guides
+            .iter()
+            .map(|&(from, to)| self.world_strip(&[from, to], [0.4, 0.7, 1.0, 0.6]))
+            .collect()
+    }
+
+    /// Points on the grid plane, projected onto the screen.
+    fn world_strip(&self, points: &[[f32; 2]], color: [f32; 4]) -> LineStrip {
+        let camera = self.world.read_resource::<Camera>();
+        LineStrip {
+            points: points
+                .iter()
+                .map(|&[x, y]| camera.world_to_ndc([x, y, 0.02]))
+                .collect(),
+            color,
+        }
+    }
+
+    fn population_graph(&self) -> Vec<LineStrip> {
+        let [x0, y0, x1, y1] = configs::GRAPH_RECT;
+        let frame = LineStrip {
+            points: vec![[x0, y0], [x1, y0], [x1, y1], [x0, y1], [x0, y0]],
+            color: [1.0, 1.0, 1.0, 0.5],
+        };
+
+        let statistics = self.world.read_resource::<Statistics>();
+        let samples = statistics
+            .samples()
+            .rev()
+            .take(configs::GRAPH_GENERATIONS)
+            .collect::<Vec<_>>();
+        let Some(latest) = samples.first().map(|sample| sample.generation) else {
+            return vec![frame];
+        };
+        // 가장 많았던 때가 그래프 위쪽 끝에 닿도록 맞춘다
+        let peak = samples
+            .iter()
+            .map(|sample| sample.population)
+            .max()
+            .unwrap_or_default()
+            .max(1) as f32;
+        let span = (configs::GRAPH_GENERATIONS - 1) as f32;
+        let points = samples
+            .iter()
+            .rev()
+            .map(|sample| {
+                let age = (latest - sample.generation) as f32;
+                [
+                    x1 - (x1 - x0) * (age / span).min(1.0),
+                    y0 + (y1 - y0) * sample.population as f32 / peak,
+                ]
+            })
+            .collect();
+
+        vec![
+            frame,
+            LineStrip {
+                points,
+                color: [0.3, 1.0, 0.4, 1.0],
+            },
+        ]
+    }
+
+    pub fn toggle_graph(&mut self) {
+        self.show_graph = !self.show_graph;
+    }
+
+    /// Applies `edit` to each of `cells`, in world cells. Cells off the grid are skipped.
+    pub fn edit_cells(&mut self, cells: &[[i32; 2]], edit: impl FnMut(bool) -> bool) {
+        let targets = cells
+            .iter()
+            .copied()
+            .filter_map(cell_index)
+            .collect::<HashSet<_>>();
+        let generation = self.generation();
+        apply_cell_edit(
+            &mut self.world.write_storage::<Cell>(),
+            &targets,
+            edit,
+            (
+                &mut self.world.write_resource::<CycleDetector>(),
+                &mut self.world.write_resource::<Timeline>(),
+                &mut self.world.write_resource::<History>(),
+                &mut self.world.write_resource::<Statistics>(),
+            ),
+            generation,
+            false,
+        );
+    }
+
+    pub fn undo(&mut self) {
+        let change = self.world.write_resource::<History>().undo();
+        match change {
+            Some((flips, generation)) => self.apply_history(&flips, generation),
+            None => log::info!("nothing to undo"),
+        }
+    }
+
+    pub fn redo(&mut self) {
+        let change = self.world.write_resource::<History>().redo();
+        match change {
+            Some((flips, generation)) => self.apply_history(&flips, generation),
+            None => log::info!("nothing to redo"),
+        }
+    }
+
+    /// `(first, last)` generation the timeline can go back to.
+    pub fn timeline_range(&self) -> Option<(u64, u64)> {
+        self.world.read_resource::<Timeline>().range()
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.world.read_resource::<SimulationClock>().generation
+    }
+
+    /// Goes to `target`, rebuilt from the timeline if it was computed before,
+    /// otherwise stepped forward without drawing the generations in between, up to
+    /// `MAX_JUMP_GENERATIONS` ahead. Playing stops.
+    pub fn jump_to_generation(&mut self, target: u64) -> anyhow::Result<()> {
+        let current = self.generation();
+        let recorded = self.world.read_resource::<Timeline>().grid_at(target);
+        let grid = match recorded {
+            Some(grid) => grid,
+            None if target.saturating_sub(current) > configs::MAX_JUMP_GENERATIONS => {
+                anyhow::bail!(
+                    "generation {} is more than {} ahead",
+                    target,
+                    configs::MAX_JUMP_GENERATIONS
+                )
+            }
+            None if target > current => self.fast_forward(target - current),
+            None => anyhow::bail!("generation {} is no longer kept", target),
+        };
+
+        for cell in (&mut self.world.write_storage::<Cell>()).join() {
+            cell.alive = grid.cells()[cell.index as usize];
+        }
+        self.world
+            .write_resource::<History>()
+            .record_grid(&grid, [current, target]);
+        self.world.write_resource::<Statistics>().rebase(&grid);
+        self.world.write_resource::<SimulationClock>().generation = target;
+        self.world.write_resource::<CycleDetector>().reset();
+        self.set_ready();
+        Ok(())
+    }
+
+    /// Steps a copy of the grid, keeping the timeline and statistics as if it had played.
+    fn fast_forward(&mut self, generations: u64) -> Grid {
+        let mut generation = self.generation();
+        let mut grid = read_grid(&self.world);
+        let mut timeline = self.world.write_resource::<Timeline>();
+        let mut statistics = self.world.write_resource::<Statistics>();
+        for _ in 0..generations {
+            grid = grid.step();
+            generation += 1;
+            timeline.record(generation, &grid);
+            statistics.record(generation, &grid);
+        }
+        grid
+    }
+
+    /// Moves along the timeline by `delta` generations from the current one.
+    /// Stops at the first generation still kept rather than refusing to go further back.
+    pub fn scrub(&mut self, delta: i64) {
+        let mut target = self.generation().saturating_add_signed(delta);
+        if let Some((first, _)) = self.timeline_range() {
+            target = target.max(first);
+        }
+        if let Err(e) = self.jump_to_generation(target) {
+            log::warn!("{}", e);
+        }
+    }
+
+    /// Flips cells back or forth for undo and redo. Playing stops so the step isn't taken again at once.
+    fn apply_history(&mut self, flips: &[u32], generation: u64) {
+        let flips = flips.iter().copied().collect::<HashSet<_>>();
+        for cell in (&mut self.world.write_storage::<Cell>()).join() {
+            if flips.contains(&cell.index) {
+                cell.alive = !cell.alive;
+            }
+        }
+        self.world.write_resource::<SimulationClock>().generation = generation;
+        self.world.write_resource::<CycleDetector>().reset();
+        let grid = read_grid(&self.world);
+        self.world
+            .write_resource::<Timeline>()
+            .record(generation, &grid);
+        self.world.write_resource::<Statistics>().rebase(&grid);
+        self.set_ready();
+    }
+
+    pub fn is_selecting(&self) -> bool {
+        self.world.read_resource::<Selection>().enabled
+    }
+
+    /// Switches left drags between painting and selecting. Turning it off drops the selection.
+    pub fn toggle_selection_tool(&mut self) {
+        let enabled = !self.is_selecting();
+        self.world.write_resource::<Selection>().enabled = enabled;
+        if !enabled {
+            self.clear_selection();
+        }
+    }
+
+    pub fn clear_selection(&mut self) {
+        let mut selection = self.world.write_resource::<Selection>();
+        selection.rect = None;
+        selection.anchor = None;
+        drop(selection);
+        self.watch_region(None);
+    }
+
+    fn selected_cells(&self) -> Vec<[i32; 2]> {
+        self.world.read_resource::<Selection>().cells()
+    }
+
+    /// Selected rectangle in pattern coordinates, the region snapshots are cropped to.
+    pub fn selection_region(&self) -> Option<[i32; 4]> {
+        self.world.read_resource::<Selection>().pattern_region()
+    }
+
+    /// Live cells inside the selection, `None` when nothing is selected.
+    fn selection_pattern(&self) -> Option<Pattern> {
+        let [x0, y0, x1, y1] = self.selection_region()?;
+        Some(Pattern::new(
+            live_cell_pattern(&self.world)
+                .cells()
+                .iter()
+                .copied()
+                .filter(|&[x, y]| (x0..=x1).contains(&x) && (y0..=y1).contains(&y)),
+        ))
+    }
+
+    /// Copies the selected cells to the clipboard and returns the rle.
+    pub fn copy_selection(&mut self) -> Option<String> {
+        let pattern = self.selection_pattern()?;
+        if pattern.population() == 0 {
+            return None;
+        }
+        let rle = encode_rle(&pattern);
+        self.set_clipboard(rle.clone());
+        Some(rle)
+    }
+
+    pub fn cut_selection(&mut self) -> Option<String> {
+        let rle = self.copy_selection()?;
+        self.clear_selected_cells();
+        Some(rle)
+    }
+
+    pub fn set_clipboard(&mut self, rle: String) {
+        self.clipboard = Some(rle);
+    }
+
+    /// Picks up the clipboard as a stamp, to be placed with a left click.
+    pub fn paste_clipboard(&mut self) -> anyhow::Result<()> {
+        let Some(rle) = &self.clipboard else {
+            anyhow::bail!("the clipboard is empty");
+        };
+        self.stamp = Some(Stamp::new(&parse_rle(rle)?));
+        Ok(())
+    }
+
+    pub fn clear_selected_cells(&mut self) {
+        let cells = self.selected_cells();
+        self.edit_cells(&cells, |_| false);
+    }
+
+    pub fn invert_selection(&mut self) {
+        let cells = self.selected_cells();
+        self.edit_cells(&cells, |alive| !alive);
+    }
+
+    pub fn fill_selection_random(&mut self) {
+        let cells = self.selected_cells();
+        self.edit_cells(&cells, |_| rand::random());
+    }
+
+    /// Cells the stamp would cover if it were placed now, clipped to the grid.
+    fn ghost_cells(&self) -> Vec<[i32; 2]> {
+        let Some(stamp) = &self.stamp else {
+            return vec![];
+        };
+        let half = [
+            configs::GRID_SIZE_HALF[0] as i32,
+            configs::GRID_SIZE_HALF[1] as i32,
+        ];
+        pattern_world_cells(stamp.pattern(), self.cursor_cell())
+            .into_iter()
+            .filter(|[x, y]| x.abs() <= half[0] && y.abs() <= half[1])
+            .collect()
+    }
+
+    /// Grid cell under the mouse cursor.
+    fn cursor_cell(&self) -> [i32; 2] {
+        let input_handler = self.world.read_resource::<InputHandler>();
+        let camera = self.world.read_resource::<Camera>();
+        camera.screen_to_cell(input_handler.mouse_position())
+    }
+
+    pub fn is_stamping(&self) -> bool {
+        self.stamp.is_some()
+    }
+
+    /// Picks up the selected library pattern, or drops the one being held.
+    pub fn toggle_stamp(&mut self) {
+        self.stamp = match self.stamp {
+            Some(_) => None,
+            None => Some(Stamp::new(&self.library_entry().pattern())),
+        };
+    }
+
+    pub fn stamp_mut(&mut self) -> Option<&mut Stamp> {
+        self.stamp.as_mut()
+    }
+
+    pub fn set_mirror_mode(&mut self, mode: MirrorMode) {
+        self.world.write_resource::<Mirror>().mode = mode;
+    }
+
+    pub fn cycle_mirror_mode(&mut self) {
+        let mode = self.world.read_resource::<Mirror>().mode.next();
+        self.set_mirror_mode(mode);
+    }
+
+    /// Moves the mirror center to `cell`, or to the cell under the mouse.
+    pub fn set_mirror_center(&mut self, cell: Option<[i32; 2]>) {
+        let center = cell.unwrap_or_else(|| self.cursor_cell());
+        self.world.write_resource::<Mirror>().center = center;
+    }
+
+    /// Action picked from the long-press menu, with the cell it was opened at.
+    pub fn take_context_action(&mut self) -> Option<(ContextAction, [i32; 2])> {
+        self.world.write_resource::<ContextMenu>().chosen.take()
+    }
+
+    /// Selects the live cells connected to `cell`, diagonals included,
+    /// unless the selection already covers it. `false` when neither holds anything at `cell`.
+    pub fn select_object_at(&mut self, cell: [i32; 2]) -> bool {
+        let [x, y] = cell;
+        let covered = self
+            .world
+            .read_resource::<Selection>()
+            .rect
+            .map_or(false, |[x0, y0, x1, y1]| {
+                (x0..=x1).contains(&x) && (y0..=y1).contains(&y)
+            });
+        if covered {
+            return true;
+        }
+
+        let grid = read_grid(&self.world);
+        let alive = |cell| cell_index(cell).map_or(false, |index| grid.cells()[index as usize]);
+        if !alive(cell) {
+            return false;
+        }
+        let mut object = HashSet::from([cell]);
+        let mut stack = vec![cell];
+        while let Some([x, y]) = stack.pop() {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let next = [x + dx, y + dy];
+                    if alive(next) && object.insert(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+        let rect = object
+            .iter()
+            .fold([x, y, x, y], |[x0, y0, x1, y1], &[x, y]| {
+                [x0.min(x), y0.min(y), x1.max(x), y1.max(y)]
+            });
+        let mut selection = self.world.write_resource::<Selection>();
+        selection.rect = Some(rect);
+        let region = selection.pattern_region();
+        drop(selection);
+        self.watch_region(region);
+        true
+    }
+
+    /// Places the clipboard centered on `cell`, without picking it up as a stamp.
+    pub fn paste_at(&mut self, cell: [i32; 2]) -> anyhow::Result<()> {
+        let Some(rle) = &self.clipboard else {
+            anyhow::bail!("the clipboard is empty");
+        };
+        let pattern = parse_rle(rle)?;
+        self.place_pattern(&pattern, cell);
+        Ok(())
+    }
+
+    /// Places the selected library pattern centered on `cell`.
+    pub fn stamp_at(&mut self, cell: [i32; 2]) {
+        let pattern = self.library_entry().pattern();
+        self.place_pattern(&pattern, cell);
+    }
+
+    /// Adds the live cells of `pattern` centered on `cell`.
+    pub fn place_pattern(&mut self, pattern: &Pattern, cell: [i32; 2]) {
+        let cells = pattern_world_cells(pattern, cell);
+        self.edit_cells(&cells, |_| true);
+    }
+
+    /// Adds the object an apgcode describes at the grid center.
+    pub fn spawn_apgcode(&mut self, code: &str) -> anyhow::Result<()> {
+        let pattern = decode_apgcode(code)?;
+        self.place_pattern(&pattern, [0, 0]);
+        Ok(())
+    }
+
+    /// Adds the held pattern to the grid under the cursor. The stamp stays in hand for repeated placing.
+    pub fn place_stamp(&mut self) {
+        let Some(stamp) = &self.stamp else {
+            return;
+        };
+        let cells = pattern_world_cells(stamp.pattern(), self.cursor_cell());
+        self.edit_cells(&cells, |_| true);
+    }
+
+    /// Heads-up display in the top left corner, in screen units so the camera doesn't move it.
     pub fn get_text_data(&self) -> Vec<TextAttributes> {
-        vec![]
+        let cells = self.world.read_storage::<Cell>();
+        let population = cells.join().filter(|cell| cell.alive).count();
+        let clock = self.world.read_resource::<SimulationClock>();
+        let rule = self.world.read_resource::<Rule>();
+        let boundary = self.world.read_resource::<Boundary>();
+
+        let mut content = format!(
+            "Gen: {}\nPop: {}\nRule: {} {}\n",
+            clock.generation,
+            population,
+            *rule,
+            boundary.name()
+        );
+        content += &format!("Speed: {}\n", clock.speed);
+        content += &format!("Phase: {}", self.phase_name());
+        if self.is_editing() {
+            content += &format!(", {}", self.world.read_resource::<DrawTool>().tool.name());
+        }
+        let mirror = self.world.read_resource::<Mirror>();
+        if mirror.mode != MirrorMode::Off {
+            content += &format!(
+                "\nMirror: {} at ({}, {})",
+                mirror.mode.name(),
+                mirror.center[0],
+                mirror.center[1]
+            );
+        }
+        if let Some((_, cycle)) = self.cycle() {
+            content += &format!("\nCycle: {}", cycle);
+        }
+        if let Some((generation, reason)) = self.last_stop() {
+            content += &format!("\nStopped at {}: {}", generation, reason);
+        }
+
+        let aspect_ratio = self.screen_aspect_ratio();
+        let mut texts = vec![TextAttributes {
+            content,
+            color: [1.0, 1.0, 1.0],
+            position: [-aspect_ratio + 0.05, 0.92, 0.0],
+            size: 0.05,
+        }];
+        let menu = self.world.read_resource::<ContextMenu>();
+        if menu.cell.is_some() {
+            // 화면 단위는 가로가 화면비만큼 늘어나 있다
+            texts.extend(
+                menu.items()
+                    .map(|(action, [x0, y0, _, y1])| TextAttributes {
+                        content: action.to_string(),
+                        color: [1.0, 0.85, 0.2],
+                        position: [x0 * aspect_ratio + 0.03, (y0 + y1) * 0.5, 0.0],
+                        size: 0.05,
+                    }),
+            );
+        }
+        texts
+    }
+
+    fn phase_name(&self) -> &'static str {
+        match self.phase {
+            Phase::Ready => "Ready",
+            Phase::Edit => "Edit",
+            Phase::Play => match self.world.read_resource::<SimulationClock>().state {
+                ClockState::Paused => "Paused",
+                ClockState::Running => "Playing",
+                ClockState::Stepping(_) => "Stepping",
+            },
+        }
     }
 
-    #[allow(unused)]
     pub fn restart(&mut self) {
-        self.world.delete_all();
         self.init_game();
     }
 
-    pub fn start_auto_playing(&mut self, tick: f32) {
-        let mut stage_tick = self.world.write_resource::<StageTick>();
-        stage_tick.stage_tick = tick;
-        stage_tick.current_spent = 0.0;
-        self.phase = Phase::Play;
+    pub fn library_entry(&self) -> &'static LibraryEntry {
+        &LIBRARY[self.library_entry]
+    }
+
+    pub fn select_pattern(&mut self, name: &str) -> anyhow::Result<()> {
+        let Some(index) = find_entry(name) else {
+            anyhow::bail!("unknown pattern {}", name);
+        };
+        self.library_entry = index;
+        self.soup = None;
+        self.restart();
+        Ok(())
+    }
+
+    /// Restarts from a random soup, and keeps restarting from the same one.
+    pub fn reset_with_soup(&mut self, options: SoupOptions) {
+        self.soup = Some(options);
+        self.restart();
+    }
+
+    /// Steps through the library, wrapping around at both ends.
+    pub fn cycle_pattern(&mut self, forward: bool) {
+        let step = if forward { 1 } else { LIBRARY.len() - 1 };
+        self.library_entry = (self.library_entry + step) % LIBRARY.len();
+        self.soup = None;
+        self.restart();
+    }
+
+    /// Replaces the whole grid with `pattern`, centered on the origin.
+    /// The previous grid stays in the undo history.
+    pub fn load_pattern(&mut self, pattern: &Pattern) {
+        self.set_ready();
+        let generation =
+            std::mem::take(&mut self.world.write_resource::<SimulationClock>().generation);
+        self.world.write_resource::<CycleDetector>().reset();
+        self.last_stop = None;
+        self.world.delete_all();
+        cell_grid(&mut self.world);
+        set_pattern_alive(&mut self.world, pattern, [0, 0]);
+        self.restart_statistics();
+        let grid = read_grid(&self.world);
+        self.world
+            .write_resource::<History>()
+            .record_grid(&grid, [generation, 0]);
+        self.world.write_resource::<Timeline>().reset(0, &grid);
+    }
+
+    pub fn set_rule(&mut self, rule: Rule, boundary: Boundary) {
+        self.world.insert(rule);
+        self.world.insert(boundary);
+    }
+
+    pub fn session(&self) -> Session {
+        let clock = self.world.read_resource::<SimulationClock>();
+        let camera = self.world.read_resource::<Camera>();
+        Session {
+            rule: *self.world.read_resource::<Rule>(),
+            boundary: *self.world.read_resource::<Boundary>(),
+            generation: clock.generation,
+            speed: clock.speed,
+            eye: camera.eye(),
+            target: camera.target(),
+            phase: match self.phase {
+                Phase::Ready => SessionPhase::Ready,
+                Phase::Edit => SessionPhase::Edit(self.world.read_resource::<DrawTool>().tool),
+                Phase::Play => match clock.state {
+                    ClockState::Paused => SessionPhase::Paused,
+                    ClockState::Running => SessionPhase::Playing,
+                    ClockState::Stepping(generations) => SessionPhase::Stepping(generations),
+                },
+            },
+            pattern: live_cell_pattern(&self.world),
+        }
+    }
+
+    /// Throws the current world away and rebuilds it from `session`.
+    /// A held stamp and the selection belong to the old grid and are dropped.
+    pub fn load_session(&mut self, session: &Session) {
+        self.world.write_resource::<CycleDetector>().reset();
+        self.last_stop = None;
+        self.stamp = None;
+        self.clear_selection();
+        self.phase = Phase::Ready;
+        self.world.write_resource::<DrawTool>().editing = false;
+        self.world.delete_all();
+        cell_grid(&mut self.world);
+        set_pattern_alive_in_place(&mut self.world, &session.pattern);
+
+        self.set_rule(session.rule, session.boundary);
+        let mut clock = SimulationClock::default();
+        clock.speed = session.speed;
+        clock.generation = session.generation;
+        self.world.insert(clock);
+        self.world
+            .write_resource::<Camera>()
+            .set_view(session.eye, session.target);
+        self.restart_statistics();
+        let grid = read_grid(&self.world);
+        self.world
+            .write_resource::<Timeline>()
+            .reset(session.generation, &grid);
+        self.world.write_resource::<History>().reset(grid);
+        match session.phase {
+            SessionPhase::Ready => self.set_ready(),
+            SessionPhase::Paused => {
+                self.set_playing();
+                self.stop();
+            }
+            SessionPhase::Playing => self.start_auto_playing(),
+            SessionPhase::Stepping(generations) => self.next(generations),
+            SessionPhase::Edit(tool) => self.select_tool(tool),
+        }
+    }
+
+    /// Cycle the current generation belongs to, found by watching the steps so far.
+    pub fn cycle(&self) -> Option<(u64, Cycle)> {
+        self.world.read_resource::<CycleDetector>().cycle()
+    }
+
+    /// Limits cycle detection to `region` in pattern coordinates, or watches the whole grid again.
+    pub fn watch_region(&mut self, region: Option<[i32; 4]>) {
+        self.world
+            .write_resource::<CycleDetector>()
+            .set_region(region);
+    }
+
+    pub fn last_stop(&self) -> Option<(u64, StopReason)> {
+        self.last_stop
+    }
+
+    pub fn auto_stop_mut(&mut self) -> specs::shred::FetchMut<AutoStop> {
+        self.world.write_resource::<AutoStop>()
+    }
+
+    pub fn seed_from_image(
+        &mut self,
+        bytes: &[u8],
+        options: &ImageSeedOptions,
+    ) -> anyhow::Result<()> {
+        let pattern = pattern_from_image(bytes, options)?;
+        self.load_pattern(&pattern);
+        Ok(())
+    }
+
+    /// PNG of the current generation, either the whole grid or just `region`.
+    pub fn snapshot_png(
+        &self,
+        region: Option<[i32; 4]>,
+        options: &SnapshotOptions,
+    ) -> anyhow::Result<Vec<u8>> {
+        let grid = read_grid(&self.world);
+        let region = region.unwrap_or_else(|| grid.region());
+        encode_png(&render_snapshot(&grid.to_pattern(), region, options))
+    }
+
+    /// Runs a copy of the grid forward without touching the world.
+    pub fn record_animation(&self, options: &AnimationOptions) -> anyhow::Result<Vec<u8>> {
+        record_animation(&read_grid(&self.world), options)
+    }
+
+    /// apgcode of the selected cells, or of everything alive when nothing is selected,
+    /// if they are a single still life, oscillator or spaceship.
+    pub fn apgcode(&self) -> Option<String> {
+        let pattern = self
+            .selection_pattern()
+            .unwrap_or_else(|| live_cell_pattern(&self.world));
+        encode_apgcode(
+            &pattern,
+            configs::MAX_OBJECT_PERIOD,
+            &self.world.read_resource::<Rule>(),
+        )
+    }
+
+    /// Objects currently on the grid, counted by type.
+    pub fn census(&self) -> Vec<CensusEntry> {
+        census(
+            &live_cell_pattern(&self.world),
+            configs::MAX_OBJECT_PERIOD,
+            &self.world.read_resource::<Rule>(),
+        )
+    }
+
+    /// Drops the recorded history and starts again from the current generation.
+    fn restart_statistics(&mut self) {
+        let generation = self.generation();
+        let grid = read_grid(&self.world);
+        let mut statistics = self.world.write_resource::<Statistics>();
+        statistics.clear();
+        statistics.record(generation, &grid);
+    }
+
+    pub fn statistics(&self) -> specs::shred::Fetch<Statistics> {
+        self.world.read_resource::<Statistics>()
+    }
+
+    pub fn start_auto_playing(&mut self) {
+        self.world.write_resource::<SimulationClock>().run();
+        self.set_playing();
     }
 
     pub fn stop(&mut self) {
-        let mut stage_tick = self.world.write_resource::<StageTick>();
-        stage_tick.stage_tick = 99999.9;
-        stage_tick.current_spent = 0.0;
-        self.phase = Phase::Play;
+        self.world.write_resource::<SimulationClock>().pause();
     }
 
-    pub fn next(&mut self) {
-        let mut stage_tick = self.world.write_resource::<StageTick>();
-        stage_tick.stage_tick = 99999.9;
-        stage_tick.current_spent = 99999.8;
-        self.phase = Phase::Play;
+    /// Runs `generations` more, spread over frames when there are many, then pauses.
+    pub fn next(&mut self, generations: u64) {
+        self.world
+            .write_resource::<SimulationClock>()
+            .step(generations);
+        self.set_playing();
+    }
+
+    /// Plays or pauses, whichever it isn't doing.
+    pub fn toggle_playing(&mut self) {
+        if matches!(self.phase, Phase::Play)
+            && self.world.read_resource::<SimulationClock>().is_running()
+        {
+            self.stop();
+        } else {
+            self.start_auto_playing();
+        }
+    }
+
+    pub fn set_speed(&mut self, speed: Speed) {
+        self.world.write_resource::<SimulationClock>().speed = speed.clamped();
+    }
+
+    pub fn scale_speed(&mut self, faster: bool) {
+        self.world
+            .write_resource::<SimulationClock>()
+            .scale_speed(faster);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_to_jump_too_far_ahead() {
+        let mut game_state = GameState::default();
+        game_state.init();
+        assert!(game_state.jump_to_generation(u64::MAX).is_err());
+        assert!(game_state
+            .jump_to_generation(configs::MAX_JUMP_GENERATIONS + 1)
+            .is_err());
+        assert_eq!(game_state.generation(), 0);
+
+        game_state.jump_to_generation(10).unwrap();
+        assert_eq!(game_state.generation(), 10);
+        game_state.jump_to_generation(3).unwrap();
+        assert_eq!(game_state.generation(), 3);
+    }
+
+    #[test]
+    fn edits_are_not_counted_as_births_or_deaths() {
+        let mut game_state = GameState::default();
+        game_state.init();
+        // 빈 칸 하나를 살리고, 살아 있던 칸은 모두 지운다
+        let alive = live_cell_pattern(&game_state.world).cells().to_vec();
+        game_state.edit_cells(&[[20, 20]], |_| true);
+        game_state.edit_cells(&alive, |_| false);
+
+        game_state.next(1);
+        game_state.update(0.0);
+
+        let statistics = game_state.statistics();
+        let last = statistics.samples().last().unwrap();
+        assert_eq!(last.generation, 1);
+        // 혼자 남은 칸이 죽은 것만 센다
+        assert_eq!((last.population, last.births, last.deaths), (0, 0, 1));
+    }
+
+    #[test]
+    fn sessions_keep_the_phase_and_drop_the_stamp_and_selection() {
+        let mut game_state = GameState::default();
+        game_state.init();
+        for phase in [
+            SessionPhase::Ready,
+            SessionPhase::Paused,
+            SessionPhase::Playing,
+            SessionPhase::Stepping(5),
+            SessionPhase::Edit(Tool::Ellipse),
+        ] {
+            game_state.toggle_stamp();
+            game_state.world.write_resource::<Selection>().rect = Some([0, 0, 3, 3]);
+
+            let mut session = game_state.session();
+            session.phase = phase;
+            game_state.load_session(&session);
+
+            assert_eq!(game_state.session().phase, phase);
+            assert!(!game_state.is_stamping());
+            assert_eq!(game_state.selection_region(), None);
+        }
+    }
+
+    #[test]
+    fn lays_text_out_for_the_window_size() {
+        let mut game_state = GameState::default();
+        game_state.init();
+        game_state.set_screen_size(winit::dpi::PhysicalSize::new(400, 100));
+        assert_eq!(game_state.screen_aspect_ratio(), 4.0);
+        assert_eq!(game_state.get_text_data()[0].position[0], -4.0 + 0.05);
     }
 }
@@ -1,4 +1,8 @@
 #[cfg(target_arch = "wasm32")]
+use crate::pattern::{parse_seed, AnimationFormat, ImageSeedOptions, SoupOptions, LIBRARY};
+#[cfg(target_arch = "wasm32")]
+use crate::resources::{MirrorMode, Speed, Tool};
+#[cfg(target_arch = "wasm32")]
 use lazy_static::lazy_static;
 #[cfg(target_arch = "wasm32")]
 use std::sync::{Arc, Mutex};
@@ -6,6 +10,21 @@ use std::sync::{Arc, Mutex};
 #[cfg(target_arch = "wasm32")]
 pub struct JsBinding {
     buttons: Vec<Mutex<bool>>,
+    apgcode: Mutex<Option<String>>,
+    image: Mutex<Option<(Vec<u8>, ImageSeedOptions)>>,
+    animation_format: Mutex<Option<AnimationFormat>>,
+    pattern_name: Mutex<Option<String>>,
+    soup: Mutex<Option<SoupOptions>>,
+    session: Mutex<Option<String>>,
+    auto_stop: Mutex<Option<(bool, Option<u32>, Option<u64>)>>,
+    paste: Mutex<Option<String>>,
+    jump: Mutex<Option<u64>>,
+    speed: Mutex<Option<Speed>>,
+    steps: Mutex<Option<u64>>,
+    tool: Mutex<Option<Tool>>,
+    mirror: Mutex<Option<(MirrorMode, [i32; 2])>>,
+    key_map: Mutex<Option<String>>,
+    font: Mutex<Option<Vec<u8>>>,
     // btn_reset: Mutex<bool>,
 }
 #[cfg(target_arch = "wasm32")]
@@ -22,6 +41,141 @@ impl JsBinding {
         *state
     }
 
+    fn set_apgcode(&self, code: String) {
+        let mut apgcode = self.apgcode.lock().unwrap();
+        *apgcode = Some(code);
+    }
+
+    pub fn take_apgcode(&self) -> Option<String> {
+        self.apgcode.lock().unwrap().take()
+    }
+
+    fn set_image(&self, bytes: Vec<u8>, options: ImageSeedOptions) {
+        let mut image = self.image.lock().unwrap();
+        *image = Some((bytes, options));
+    }
+
+    pub fn take_image(&self) -> Option<(Vec<u8>, ImageSeedOptions)> {
+        self.image.lock().unwrap().take()
+    }
+
+    fn set_animation_format(&self, format: AnimationFormat) {
+        let mut animation_format = self.animation_format.lock().unwrap();
+        *animation_format = Some(format);
+    }
+
+    pub fn take_animation_format(&self) -> Option<AnimationFormat> {
+        self.animation_format.lock().unwrap().take()
+    }
+
+    fn set_pattern_name(&self, name: String) {
+        let mut pattern_name = self.pattern_name.lock().unwrap();
+        *pattern_name = Some(name);
+    }
+
+    pub fn take_pattern_name(&self) -> Option<String> {
+        self.pattern_name.lock().unwrap().take()
+    }
+
+    fn set_soup(&self, options: SoupOptions) {
+        let mut soup = self.soup.lock().unwrap();
+        *soup = Some(options);
+    }
+
+    pub fn take_soup(&self) -> Option<SoupOptions> {
+        self.soup.lock().unwrap().take()
+    }
+
+    fn set_session(&self, text: String) {
+        let mut session = self.session.lock().unwrap();
+        *session = Some(text);
+    }
+
+    pub fn take_session(&self) -> Option<String> {
+        self.session.lock().unwrap().take()
+    }
+
+    fn set_auto_stop(&self, conditions: (bool, Option<u32>, Option<u64>)) {
+        let mut auto_stop = self.auto_stop.lock().unwrap();
+        *auto_stop = Some(conditions);
+    }
+
+    pub fn take_auto_stop(&self) -> Option<(bool, Option<u32>, Option<u64>)> {
+        self.auto_stop.lock().unwrap().take()
+    }
+
+    fn set_paste(&self, rle: String) {
+        let mut paste = self.paste.lock().unwrap();
+        *paste = Some(rle);
+    }
+
+    pub fn take_paste(&self) -> Option<String> {
+        self.paste.lock().unwrap().take()
+    }
+
+    fn set_jump(&self, generation: u64) {
+        let mut jump = self.jump.lock().unwrap();
+        *jump = Some(generation);
+    }
+
+    pub fn take_jump(&self) -> Option<u64> {
+        self.jump.lock().unwrap().take()
+    }
+
+    fn set_speed(&self, value: Speed) {
+        let mut speed = self.speed.lock().unwrap();
+        *speed = Some(value);
+    }
+
+    pub fn take_speed(&self) -> Option<Speed> {
+        self.speed.lock().unwrap().take()
+    }
+
+    fn set_steps(&self, generations: u64) {
+        let mut steps = self.steps.lock().unwrap();
+        *steps = Some(generations);
+    }
+
+    pub fn take_steps(&self) -> Option<u64> {
+        self.steps.lock().unwrap().take()
+    }
+
+    fn set_tool(&self, value: Tool) {
+        let mut tool = self.tool.lock().unwrap();
+        *tool = Some(value);
+    }
+
+    pub fn take_tool(&self) -> Option<Tool> {
+        self.tool.lock().unwrap().take()
+    }
+
+    fn set_mirror(&self, value: (MirrorMode, [i32; 2])) {
+        let mut mirror = self.mirror.lock().unwrap();
+        *mirror = Some(value);
+    }
+
+    pub fn take_mirror(&self) -> Option<(MirrorMode, [i32; 2])> {
+        self.mirror.lock().unwrap().take()
+    }
+
+    fn set_key_map(&self, text: String) {
+        let mut key_map = self.key_map.lock().unwrap();
+        *key_map = Some(text);
+    }
+
+    pub fn take_key_map(&self) -> Option<String> {
+        self.key_map.lock().unwrap().take()
+    }
+
+    fn set_font(&self, bytes: Vec<u8>) {
+        let mut font = self.font.lock().unwrap();
+        *font = Some(bytes);
+    }
+
+    pub fn take_font(&self) -> Option<Vec<u8>> {
+        self.font.lock().unwrap().take()
+    }
+
     pub fn reset(&self) {
         for button in &self.buttons {
             let mut state = button.lock().unwrap();
@@ -38,7 +192,37 @@ lazy_static! {
             Mutex::new(false),
             Mutex::new(false),
             Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
+            Mutex::new(false),
         ],
+        apgcode: Mutex::new(None),
+        image: Mutex::new(None),
+        animation_format: Mutex::new(None),
+        pattern_name: Mutex::new(None),
+        soup: Mutex::new(None),
+        session: Mutex::new(None),
+        auto_stop: Mutex::new(None),
+        paste: Mutex::new(None),
+        jump: Mutex::new(None),
+        speed: Mutex::new(None),
+        steps: Mutex::new(None),
+        tool: Mutex::new(None),
+        mirror: Mutex::new(None),
+        key_map: Mutex::new(None),
+        font: Mutex::new(None),
     });
 }
 
@@ -47,13 +231,171 @@ pub fn set_btn_start_game(btn_index: usize, new_state: bool) {
     JS_BINDING.set_state(btn_index, new_state);
 }
 
+#[cfg(target_arch = "wasm32")]
+pub use wasm::{copy_selection, copy_to_clipboard, download_file, show_census, update_timeline};
+
 #[cfg(target_arch = "wasm32")]
 mod wasm {
     use super::*;
     use wasm_bindgen::prelude::*;
 
+    // index.html 에 정의되어 있음
+    #[wasm_bindgen]
+    extern "C" {
+        pub fn copy_to_clipboard(text: &str);
+        pub fn download_file(name: &str, bytes: &[u8]);
+        pub fn show_census(report: &str);
+        pub fn copy_selection(rle: &str);
+        pub fn update_timeline(first: f64, last: f64, current: f64);
+    }
+
     #[wasm_bindgen]
     pub fn set_state_from_js(btn_type: usize, pressed: bool) {
         set_btn_start_game(btn_type, pressed);
     }
+
+    #[wasm_bindgen]
+    pub fn spawn_apgcode_from_js(code: String) {
+        JS_BINDING.set_apgcode(code);
+    }
+
+    #[wasm_bindgen]
+    pub fn select_pattern_from_js(name: String) {
+        JS_BINDING.set_pattern_name(name);
+    }
+
+    /// one `name\tcategory` line per library entry
+    #[wasm_bindgen]
+    pub fn pattern_library_from_js() -> String {
+        LIBRARY
+            .iter()
+            .map(|entry| format!("{}\t{}", entry.name, entry.category.name()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// an empty seed picks a random one
+    #[wasm_bindgen]
+    pub fn reset_with_soup_from_js(seed: String, density: f32, symmetry: String) {
+        let symmetry = match symmetry.parse() {
+            Ok(symmetry) => symmetry,
+            Err(e) => {
+                log::warn!("{}", e);
+                return;
+            }
+        };
+        let seed = if seed.trim().is_empty() {
+            rand::random()
+        } else {
+            parse_seed(&seed)
+        };
+        log::info!("soup seed : {}", seed);
+        JS_BINDING.set_soup(SoupOptions {
+            seed,
+            density,
+            symmetry,
+            ..Default::default()
+        });
+    }
+
+    /// rle to pick up as a stamp, from the page or another program
+    #[wasm_bindgen]
+    pub fn paste_rle_from_js(rle: String) {
+        JS_BINDING.set_paste(rle);
+    }
+
+    /// scrubbing the timeline and typing a generation both end up here
+    #[wasm_bindgen]
+    pub fn jump_to_generation_from_js(generation: f64) {
+        JS_BINDING.set_jump(generation.max(0.0) as u64);
+    }
+
+    /// `mode` as shown in the hud, e.g. "4-fold", around the world cell `x`, `y`
+    #[wasm_bindgen]
+    pub fn set_mirror_from_js(mode: String, x: i32, y: i32) {
+        match MirrorMode::ALL
+            .iter()
+            .find(|candidate| candidate.name() == mode)
+        {
+            Some(&mode) => JS_BINDING.set_mirror((mode, [x, y])),
+            None => log::warn!("unknown mirror mode {}", mode),
+        }
+    }
+
+    /// generations per second, or per frame when `per_frame` is set
+    #[wasm_bindgen]
+    pub fn set_speed_from_js(value: f32, per_frame: bool) {
+        let speed = if per_frame {
+            Speed::PerFrame(value as u32)
+        } else {
+            Speed::PerSecond(value)
+        };
+        JS_BINDING.set_speed(speed.clamped());
+    }
+
+    #[wasm_bindgen]
+    pub fn step_from_js(generations: u32) {
+        JS_BINDING.set_steps(generations as u64);
+    }
+
+    /// `name` as shown in the hud, e.g. "filled ellipse"
+    #[wasm_bindgen]
+    pub fn select_tool_from_js(name: String) {
+        match name.parse() {
+            Ok(tool) => JS_BINDING.set_tool(tool),
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+
+    /// same `action = Key` lines as the native keys.cfg
+    #[wasm_bindgen]
+    pub fn load_key_map_from_js(text: String) {
+        JS_BINDING.set_key_map(text);
+    }
+
+    /// ttf or otf bytes, used for characters like Hangul that the bundled font lacks
+    #[wasm_bindgen]
+    pub fn load_font_from_js(bytes: Vec<u8>) {
+        JS_BINDING.set_font(bytes);
+    }
+
+    #[wasm_bindgen]
+    pub fn load_session_from_js(text: String) {
+        JS_BINDING.set_session(text);
+    }
+
+    /// 0 turns the period or generation condition off
+    #[wasm_bindgen]
+    pub fn set_auto_stop_from_js(on_extinction: bool, max_period: u32, generation_limit: u32) {
+        JS_BINDING.set_auto_stop((
+            on_extinction,
+            Some(max_period).filter(|&period| period > 0),
+            Some(generation_limit as u64).filter(|&generation| generation > 0),
+        ));
+    }
+
+    #[wasm_bindgen]
+    pub fn record_animation_from_js(format: String) {
+        match format.parse() {
+            Ok(format) => JS_BINDING.set_animation_format(format),
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn seed_from_image_from_js(bytes: Vec<u8>, dithering: String, invert: bool) {
+        let dithering = match dithering.parse() {
+            Ok(dithering) => dithering,
+            Err(e) => {
+                log::warn!("{}", e);
+                return;
+            }
+        };
+        let options = ImageSeedOptions {
+            dithering,
+            invert,
+            ..Default::default()
+        };
+        JS_BINDING.set_image(bytes, options);
+    }
 }
@@ -11,8 +11,10 @@ mod configs;
 mod entity_builder;
 mod game_state;
 mod js_binding;
+mod pattern;
 mod renderer;
 mod resources;
+mod session;
 mod system;
 
 #[cfg(target_arch = "wasm32")]
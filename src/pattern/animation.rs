@@ -0,0 +1,214 @@
+use anyhow::*;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::pattern::{render_snapshot, Grid, SnapshotOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationFormat {
+    Gif,
+    Apng,
+}
+
+#[derive(Debug, Clone)]
+pub struct AnimationOptions {
+    /// counted from the current state, so `0..10` records it and the next 9 generations
+    pub generations: Range<u32>,
+    pub frame_delay_ms: u32,
+    /// crop every frame to the bounding box of all recorded generations
+    pub crop_to_pattern: bool,
+    pub snapshot: SnapshotOptions,
+    pub format: AnimationFormat,
+}
+
+impl AnimationFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AnimationFormat::Gif => "gif",
+            AnimationFormat::Apng => "png",
+        }
+    }
+}
+
+impl FromStr for AnimationFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "gif" => Ok(AnimationFormat::Gif),
+            "apng" => Ok(AnimationFormat::Apng),
+            _ => bail!("unknown animation format {}", s),
+        }
+    }
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        AnimationOptions {
+            generations: 0..100,
+            frame_delay_ms: 100,
+            crop_to_pattern: true,
+            snapshot: SnapshotOptions::default(),
+            format: AnimationFormat::Gif,
+        }
+    }
+}
+
+pub fn record_animation(grid: &Grid, options: &AnimationOptions) -> Result<Vec<u8>> {
+    if options.generations.is_empty() {
+        bail!("no generations to record");
+    }
+
+    let mut grid = grid.clone();
+    for _ in 0..options.generations.start {
+        grid = grid.step();
+    }
+    let mut patterns = vec![];
+    for _ in options.generations.clone() {
+        patterns.push(grid.to_pattern());
+        grid = grid.step();
+    }
+
+    let mut region = grid.region();
+    if options.crop_to_pattern {
+        let bounds = patterns
+            .iter()
+            .filter_map(|pattern| pattern.bounding_box())
+            .reduce(|a, b| {
+                [
+                    a[0].min(b[0]),
+                    a[1].min(b[1]),
+                    a[2].max(b[2]),
+                    a[3].max(b[3]),
+                ]
+            });
+        if let Some(bounds) = bounds {
+            region = bounds;
+        }
+    }
+
+    let frames = patterns
+        .iter()
+        .map(|pattern| render_snapshot(pattern, region, &options.snapshot))
+        .collect::<Vec<_>>();
+
+    match options.format {
+        AnimationFormat::Gif => encode_gif(&frames, options),
+        AnimationFormat::Apng => encode_apng(&frames, options),
+    }
+}
+
+fn encode_apng(frames: &[RgbaImage], options: &AnimationOptions) -> Result<Vec<u8>> {
+    let (width, height) = frames[0].dimensions();
+    let mut bytes = vec![];
+    {
+        let mut encoder = png::Encoder::new(&mut bytes, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(frames.len() as u32, 0)?;
+        encoder.set_frame_delay(options.frame_delay_ms.min(u16::MAX as u32) as u16, 1000)?;
+
+        let mut writer = encoder.write_header()?;
+        for frame in frames {
+            writer.write_image_data(frame.as_raw())?;
+        }
+        writer.finish()?;
+    }
+    Ok(bytes)
+}
+
+fn encode_gif(frames: &[RgbaImage], options: &AnimationOptions) -> Result<Vec<u8>> {
+    let (width, height) = frames[0].dimensions();
+    if width > u16::MAX as u32 || height > u16::MAX as u32 {
+        bail!("animation is too large for gif : {}x{}", width, height);
+    }
+    let delay = Delay::from_numer_denom_ms(options.frame_delay_ms, 1);
+
+    let mut bytes = vec![];
+    {
+        // snapshot 은 palette 색만 쓰니까 양자화 없이 그 색 그대로 들어간다
+        let mut encoder = GifEncoder::new(&mut bytes);
+        encoder.set_repeat(Repeat::Infinite)?;
+        encoder.encode_frames(
+            frames
+                .iter()
+                .map(|frame| Frame::from_parts(frame.clone(), 0, 0, delay)),
+        )?;
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifDecoder;
+    use image::codecs::png::PngDecoder;
+    use image::AnimationDecoder;
+    use std::io::Cursor;
+
+    fn blinker() -> Grid {
+        let mut grid = Grid::new(7, 7);
+        for row in 2..5 {
+            grid.set(3 * 7 + row, true);
+        }
+        grid
+    }
+
+    fn decode(bytes: &[u8], format: AnimationFormat) -> Vec<RgbaImage> {
+        let frames = match format {
+            AnimationFormat::Gif => GifDecoder::new(Cursor::new(bytes))
+                .unwrap()
+                .into_frames()
+                .collect_frames(),
+            AnimationFormat::Apng => PngDecoder::new(Cursor::new(bytes))
+                .unwrap()
+                .apng()
+                .unwrap()
+                .into_frames()
+                .collect_frames(),
+        };
+        frames
+            .unwrap()
+            .into_iter()
+            .map(Frame::into_buffer)
+            .collect()
+    }
+
+    #[test]
+    fn decodes_to_every_generation_cropped_to_the_pattern() {
+        for format in [AnimationFormat::Gif, AnimationFormat::Apng] {
+            let options = AnimationOptions {
+                generations: 1..4,
+                snapshot: SnapshotOptions {
+                    cell_size: 2,
+                    ..Default::default()
+                },
+                format,
+                ..Default::default()
+            };
+            let bytes = record_animation(&blinker(), &options).unwrap();
+            let frames = decode(&bytes, format);
+            assert_eq!(frames.len(), 3, "{:?}", format);
+
+            let mut grid = blinker().step();
+            for frame in frames {
+                let expected =
+                    render_snapshot(&grid.to_pattern(), [-1, -1, 1, 1], &options.snapshot);
+                assert_eq!(frame.dimensions(), (10, 10), "{:?}", format);
+                assert!(frame == expected, "{:?} frame differs", format);
+                grid = grid.step();
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_an_empty_range() {
+        let options = AnimationOptions {
+            generations: 3..3,
+            ..Default::default()
+        };
+        assert!(record_animation(&blinker(), &options).is_err());
+    }
+}
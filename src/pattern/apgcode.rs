@@ -0,0 +1,194 @@
+use anyhow::*;
+use std::collections::HashSet;
+
+use crate::pattern::{Orientation, Pattern, Periodicity, Rule};
+
+const CHARS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Catagolue-style apgcode (`xs4_33`, `xp2_7`, `xq4_153` ...).
+/// The representation is canonical over every phase and all 8 orientations:
+/// shortest first, then ASCII order, exactly like apgsearch picks it.
+/// Like Catagolue, the code only means something together with the `rule` it was found under.
+pub fn encode_apgcode(pattern: &Pattern, max_period: u32, rule: &Rule) -> Option<String> {
+    let (prefix, period) = match pattern.periodicity(max_period, rule)? {
+        Periodicity::StillLife => (format!("xs{}", pattern.population()), 1),
+        Periodicity::Oscillator { period } => (format!("xp{}", period), period),
+        Periodicity::Spaceship { period, .. } => (format!("xq{}", period), period),
+    };
+
+    let mut best: Option<String> = None;
+    let mut phase = pattern.clone();
+    for _ in 0..period {
+        for orientation in Orientation::ALL {
+            let candidate = encode_wechsler(&phase.oriented(orientation));
+            let is_better = match &best {
+                None => true,
+                Some(best) => (candidate.len(), &candidate) < (best.len(), best),
+            };
+            if is_better {
+                best = Some(candidate);
+            }
+        }
+        phase = phase.step(rule);
+    }
+
+    Some(format!("{}_{}", prefix, best?))
+}
+
+pub fn decode_apgcode(code: &str) -> Result<Pattern> {
+    let code = code.trim();
+    let Some((prefix, body)) = code.split_once('_') else {
+        bail!("apgcode {} has no '_' separator", code);
+    };
+    let valid_prefix = prefix
+        .strip_prefix('x')
+        .and_then(|prefix| prefix.strip_prefix(['s', 'p', 'q']))
+        .map_or(false, |number| {
+            !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+        });
+    if !valid_prefix {
+        bail!("unsupported apgcode prefix {}", prefix);
+    }
+
+    let mut cells = vec![];
+    for (strip, row) in body.split('z').enumerate() {
+        let top = strip as i32 * 5;
+        let mut x = 0;
+        let mut chars = row.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                'w' => x += 2,
+                'x' => x += 3,
+                'y' => {
+                    let run = chars
+                        .next()
+                        .and_then(|c| c.to_digit(36))
+                        .ok_or_else(|| anyhow!("apgcode {} has a broken 'y' run", code))?;
+                    x += 4 + run as i32;
+                }
+                c => {
+                    let column = c
+                        .to_digit(32)
+                        .ok_or_else(|| anyhow!("invalid character {} in apgcode {}", c, code))?;
+                    for bit in 0..5 {
+                        if column & (1 << bit) != 0 {
+                            cells.push([x, top + bit]);
+                        }
+                    }
+                    x += 1;
+                }
+            }
+        }
+    }
+
+    Ok(Pattern::new(cells))
+}
+
+fn encode_wechsler(pattern: &Pattern) -> String {
+    let pattern = pattern.normalized();
+    let Some([_, _, x_max, y_max]) = pattern.bounding_box() else {
+        return String::new();
+    };
+    let alive = pattern.cells().iter().copied().collect::<HashSet<_>>();
+
+    let mut result = String::new();
+    for strip in 0..=y_max / 5 {
+        if strip != 0 {
+            result.push('z');
+        }
+        // trailing blank columns of a strip are never written
+        let mut zeroes = 0;
+        for x in 0..=x_max {
+            let mut column = 0;
+            for bit in 0..5 {
+                if alive.contains(&[x, strip * 5 + bit]) {
+                    column |= 1 << bit;
+                }
+            }
+            if column == 0 {
+                zeroes += 1;
+                continue;
+            }
+            push_zeroes(&mut result, zeroes);
+            zeroes = 0;
+            result.push(CHARS[column] as char);
+        }
+    }
+    result
+}
+
+fn push_zeroes(result: &mut String, mut zeroes: usize) {
+    while zeroes > 39 {
+        result.push_str("yz");
+        zeroes -= 39;
+    }
+    match zeroes {
+        0 => {}
+        1 => result.push('0'),
+        2 => result.push('w'),
+        3 => result.push('x'),
+        _ => {
+            result.push('y');
+            result.push(CHARS[zeroes - 4] as char);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glider() -> Pattern {
+        Pattern::new([[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]])
+    }
+
+    #[test]
+    fn encodes_still_life_oscillator_and_spaceship() {
+        let rule = Rule::default();
+        let block = Pattern::new([[0, 0], [1, 0], [0, 1], [1, 1]]);
+        let blinker = Pattern::new([[0, 0], [1, 0], [2, 0]]);
+        assert_eq!(encode_apgcode(&block, 4, &rule).as_deref(), Some("xs4_33"));
+        assert_eq!(encode_apgcode(&blinker, 4, &rule).as_deref(), Some("xp2_7"));
+        assert_eq!(
+            encode_apgcode(&glider(), 4, &rule).as_deref(),
+            Some("xq4_153")
+        );
+    }
+
+    #[test]
+    fn code_is_the_same_for_every_phase_and_orientation() {
+        let rule = Rule::default();
+        let mut phase = glider();
+        for _ in 0..4 {
+            for orientation in Orientation::ALL {
+                let code = encode_apgcode(&phase.oriented(orientation), 4, &rule);
+                assert_eq!(code.as_deref(), Some("xq4_153"));
+            }
+            phase = phase.step(&rule);
+        }
+    }
+
+    #[test]
+    fn decodes_back_to_the_same_object() {
+        let rule = Rule::default();
+        for code in ["xs4_33", "xp2_7", "xq4_153", "xs6_696", "xp15_4r4z4r4"] {
+            let pattern = decode_apgcode(code).unwrap();
+            assert_eq!(encode_apgcode(&pattern, 15, &rule).as_deref(), Some(code));
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_codes() {
+        assert!(decode_apgcode("xs4").is_err());
+        assert!(decode_apgcode("ov_33").is_err());
+        assert!(decode_apgcode("xs_33").is_err());
+        assert!(decode_apgcode("xs4_3!").is_err());
+        assert!(decode_apgcode("xs4_3y").is_err());
+    }
+
+    #[test]
+    fn gives_up_on_objects_that_do_not_repeat() {
+        let r_pentomino = Pattern::new([[1, 0], [2, 0], [0, 1], [1, 1], [1, 2]]);
+        assert_eq!(encode_apgcode(&r_pentomino, 64, &Rule::default()), None);
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::pattern::{encode_apgcode, Category, Pattern, Periodicity, Rule, LIBRARY};
+
+/// One line of a census: every object with the same apgcode counted together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CensusEntry {
+    /// `None` for objects that did not settle within the period bound
+    pub apgcode: Option<String>,
+    pub name: Option<&'static str>,
+    pub periodicity: Option<Periodicity>,
+    pub count: usize,
+}
+
+impl fmt::Display for CensusEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.apgcode, self.name) {
+            (Some(apgcode), Some(name)) => write!(f, "{} x {} ({})", self.count, name, apgcode),
+            (Some(apgcode), None) => write!(f, "{} x {}", self.count, apgcode),
+            (None, _) => write!(f, "{} x unidentified", self.count),
+        }
+    }
+}
+
+/// Splits `pattern` into objects and counts them by type, most common first.
+/// Objects are classified by running them alone on an unbounded plane under `rule`,
+/// and only named after the library under B3/S23, which the library is made for.
+pub fn census(pattern: &Pattern, max_period: u32, rule: &Rule) -> Vec<CensusEntry> {
+    let named = *rule == Rule::default();
+    let mut entries: HashMap<Option<String>, CensusEntry> = HashMap::new();
+    for object in split_objects(pattern, max_period, rule) {
+        let apgcode = encode_apgcode(&object, max_period, rule);
+        entries
+            .entry(apgcode.clone())
+            .or_insert_with(|| CensusEntry {
+                name: apgcode.as_deref().filter(|_| named).and_then(library_name),
+                periodicity: object.periodicity(max_period, rule),
+                apgcode,
+                count: 0,
+            })
+            .count += 1;
+    }
+
+    let mut entries = entries.into_values().collect::<Vec<_>>();
+    entries.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.apgcode.cmp(&b.apgcode))
+    });
+    entries
+}
+
+/// Groups the live cells into objects.
+/// Cells start out grouped by 8-connectivity, and groups close enough to touch are merged
+/// only if running them together differs from running them apart, so a pair of blocks one
+/// cell apart stays two blocks while the pieces of a pulsar become one object.
+pub fn split_objects(pattern: &Pattern, generations: u32, rule: &Rule) -> Vec<Pattern> {
+    let mut objects = vec![];
+    for cluster in clusters(pattern.cells(), 2) {
+        let parts = clusters(&cluster, 1)
+            .into_iter()
+            .map(Pattern::new)
+            .collect::<Vec<_>>();
+
+        // 서로 영향을 주는 조각끼리 union-find 로 묶는다
+        let mut parent = (0..parts.len()).collect::<Vec<_>>();
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+        for a in 0..parts.len() {
+            for b in a + 1..parts.len() {
+                if root(&mut parent, a) != root(&mut parent, b)
+                    && interacts(&parts[a], &parts[b], generations, rule)
+                {
+                    let root_a = root(&mut parent, a);
+                    let root_b = root(&mut parent, b);
+                    parent[root_a] = root_b;
+                }
+            }
+        }
+
+        let mut groups: HashMap<usize, Vec<[i32; 2]>> = HashMap::new();
+        for (index, part) in parts.iter().enumerate() {
+            groups
+                .entry(root(&mut parent, index))
+                .or_default()
+                .extend_from_slice(part.cells());
+        }
+        objects.extend(groups.into_values().map(Pattern::new));
+    }
+    objects
+}
+
+/// Cells grouped so that each cell is within `reach` (Chebyshev distance) of another in its group.
+fn clusters(cells: &[[i32; 2]], reach: i32) -> Vec<Vec<[i32; 2]>> {
+    let index_of = cells
+        .iter()
+        .enumerate()
+        .map(|(index, &cell)| (cell, index))
+        .collect::<HashMap<_, _>>();
+    let mut visited = vec![false; cells.len()];
+    let mut clusters = vec![];
+
+    for start in 0..cells.len() {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut cluster = vec![];
+        let mut stack = vec![start];
+        while let Some(index) = stack.pop() {
+            let [x, y] = cells[index];
+            cluster.push([x, y]);
+            for dy in -reach..=reach {
+                for dx in -reach..=reach {
+                    if let Some(&next) = index_of.get(&[x + dx, y + dy]) {
+                        if !visited[next] {
+                            visited[next] = true;
+                            stack.push(next);
+                        }
+                    }
+                }
+            }
+        }
+        clusters.push(cluster);
+    }
+    clusters
+}
+
+fn union(a: &Pattern, b: &Pattern) -> Pattern {
+    Pattern::new(a.cells().iter().chain(b.cells()).copied())
+}
+
+fn interacts(a: &Pattern, b: &Pattern, generations: u32, rule: &Rule) -> bool {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    for _ in 0..generations {
+        let together = union(&a, &b).step(rule);
+        a = a.step(rule);
+        b = b.step(rule);
+        if together != union(&a, &b) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Name of the library entry with this apgcode, if there is one.
+fn library_name(apgcode: &str) -> Option<&'static str> {
+    LIBRARY
+        .iter()
+        .filter(|entry| {
+            matches!(
+                entry.category,
+                Category::StillLife | Category::Oscillator | Category::Spaceship
+            )
+        })
+        .find(|entry| {
+            encode_apgcode(
+                &entry.pattern(),
+                entry.period.unwrap_or(1),
+                &Rule::default(),
+            )
+            .as_deref()
+                == Some(apgcode)
+        })
+        .map(|entry| entry.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks_and_blinker() -> Pattern {
+        Pattern::new([
+            [0, 0],
+            [1, 0],
+            [0, 1],
+            [1, 1],
+            [3, 0],
+            [4, 0],
+            [3, 1],
+            [4, 1],
+            [10, 0],
+            [11, 0],
+            [12, 0],
+        ])
+    }
+
+    #[test]
+    fn counts_objects_that_sit_close_without_touching() {
+        let entries = census(&blocks_and_blinker(), 8, &Rule::default());
+        let counted = entries
+            .iter()
+            .map(|entry| (entry.apgcode.as_deref(), entry.count))
+            .collect::<Vec<_>>();
+        assert_eq!(counted, [(Some("xs4_33"), 2), (Some("xp2_7"), 1)]);
+        assert_eq!(entries[0].periodicity, Some(Periodicity::StillLife));
+    }
+
+    #[test]
+    fn classifies_under_the_given_rule() {
+        // B36/S245 에서 블록은 살아남지 못한다
+        let rule = "B36/S245".parse().unwrap();
+        let entries = census(&blocks_and_blinker(), 8, &rule);
+        assert!(entries
+            .iter()
+            .all(|entry| entry.apgcode.as_deref() != Some("xs4_33")));
+        assert!(entries.iter().all(|entry| entry.name.is_none()));
+    }
+}
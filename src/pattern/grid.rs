@@ -0,0 +1,112 @@
+use crate::configs;
+use crate::pattern::{Boundary, Pattern, Rule};
+
+/// Headless copy of the cell grid.
+/// Cells are stored column by column starting from the bottom-left corner, the same order as
+/// `Cell::index`, and what lies past the edges is decided by `boundary`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<bool>,
+    rule: Rule,
+    boundary: Boundary,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Grid::new(configs::GRID_SIZE[0], configs::GRID_SIZE[1])
+    }
+}
+
+impl Grid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![false; width * height],
+            rule: Rule::default(),
+            boundary: Boundary::default(),
+        }
+    }
+
+    pub fn set_rule(&mut self, rule: Rule, boundary: Boundary) {
+        self.rule = rule;
+        self.boundary = boundary;
+    }
+
+    pub fn cells(&self) -> &[bool] {
+        &self.cells
+    }
+
+    pub fn set(&mut self, index: usize, alive: bool) {
+        self.cells[index] = alive;
+    }
+
+    fn half(&self) -> [i32; 2] {
+        [(self.width / 2) as i32, (self.height / 2) as i32]
+    }
+
+    /// Bounds of the grid in pattern coordinates, `[x_min, y_min, x_max, y_max]`.
+    pub fn region(&self) -> [i32; 4] {
+        let half = self.half();
+        [
+            -half[0],
+            half[1] + 1 - self.height as i32,
+            self.width as i32 - 1 - half[0],
+            half[1],
+        ]
+    }
+
+    pub fn step(&self) -> Grid {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let mut next = Grid::new(self.width, self.height);
+        next.set_rule(self.rule, self.boundary);
+
+        for x in 0..width {
+            for y in 0..height {
+                let mut neighbors = 0;
+                for dx in -1..=1 {
+                    for dy in -1..=1 {
+                        if dx == 0 && dy == 0 {
+                            continue;
+                        }
+                        let (mut nx, mut ny) = (x + dx, y + dy);
+                        if self.boundary == Boundary::Torus {
+                            nx = nx.rem_euclid(width);
+                            ny = ny.rem_euclid(height);
+                        }
+                        if (0..width).contains(&nx)
+                            && (0..height).contains(&ny)
+                            && self.cells[(nx * height + ny) as usize]
+                        {
+                            neighbors += 1;
+                        }
+                    }
+                }
+
+                let index = (x * height + y) as usize;
+                next.cells[index] = self.rule.next(self.cells[index], neighbors);
+            }
+        }
+
+        next
+    }
+
+    pub fn to_pattern(&self) -> Pattern {
+        let half = self.half();
+        let height = self.height;
+        // 패턴 좌표계는 y 가 아래쪽이라 뒤집어 준다
+        Pattern::new(
+            self.cells
+                .iter()
+                .enumerate()
+                .filter(|(_, alive)| **alive)
+                .map(|(index, _)| {
+                    let x = (index / height) as i32 - half[0];
+                    let y = (index % height) as i32 - half[1];
+                    [x, -y]
+                }),
+        )
+    }
+}
@@ -0,0 +1,194 @@
+use anyhow::*;
+use image::imageops::FilterType;
+use std::str::FromStr;
+
+use crate::configs;
+use crate::pattern::Pattern;
+
+const BAYER_4X4: [[f32; 4]; 4] = [
+    [0.0, 8.0, 2.0, 10.0],
+    [12.0, 4.0, 14.0, 6.0],
+    [3.0, 11.0, 1.0, 9.0],
+    [15.0, 7.0, 13.0, 5.0],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dithering {
+    Threshold,
+    Ordered,
+    FloydSteinberg,
+}
+
+pub struct ImageSeedOptions {
+    /// the image is scaled to fit inside this many cells, keeping its aspect ratio
+    pub size: [u32; 2],
+    /// luminance in 0..1, darker pixels become live cells
+    pub threshold: f32,
+    pub dithering: Dithering,
+    /// make bright pixels alive instead of dark ones
+    pub invert: bool,
+}
+
+impl Default for ImageSeedOptions {
+    fn default() -> Self {
+        ImageSeedOptions {
+            size: [configs::GRID_SIZE[0] as u32, configs::GRID_SIZE[1] as u32],
+            threshold: 0.5,
+            dithering: Dithering::FloydSteinberg,
+            invert: false,
+        }
+    }
+}
+
+impl FromStr for Dithering {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "threshold" => Ok(Dithering::Threshold),
+            "ordered" => Ok(Dithering::Ordered),
+            "floyd-steinberg" => Ok(Dithering::FloydSteinberg),
+            _ => bail!("unknown dithering {}", s),
+        }
+    }
+}
+
+pub fn pattern_from_image(bytes: &[u8], options: &ImageSeedOptions) -> Result<Pattern> {
+    let img = image::load_from_memory(bytes)?
+        .resize(options.size[0], options.size[1], FilterType::Triangle)
+        .to_rgba8();
+    let (width, height) = img.dimensions();
+    let (width, height) = (width as usize, height as usize);
+
+    // 투명한 부분은 흰 배경 위에 있다고 본다
+    let mut luminance = img
+        .pixels()
+        .map(|pixel| {
+            let [r, g, b, a] = pixel.0.map(|c| c as f32 / 255.0);
+            let luma = r * 0.299 + g * 0.587 + b * 0.114;
+            let luma = luma * a + (1.0 - a);
+            if options.invert {
+                1.0 - luma
+            } else {
+                luma
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut cells = vec![];
+    for y in 0..height {
+        for x in 0..width {
+            let luma = luminance[y * width + x];
+            let alive = match options.dithering {
+                Dithering::Threshold => luma < options.threshold,
+                Dithering::Ordered => {
+                    let bias = (BAYER_4X4[y % 4][x % 4] + 0.5) / 16.0 - 0.5;
+                    luma < options.threshold + bias
+                }
+                Dithering::FloydSteinberg => {
+                    let alive = luma < options.threshold;
+                    let error = luma - if alive { 0.0 } else { 1.0 };
+                    let mut spread = |dx: isize, dy: usize, weight: f32| {
+                        let nx = x as isize + dx;
+                        let ny = y + dy;
+                        if nx >= 0 && (nx as usize) < width && ny < height {
+                            luminance[ny * width + nx as usize] += error * weight;
+                        }
+                    };
+                    spread(1, 0, 7.0 / 16.0);
+                    spread(-1, 1, 3.0 / 16.0);
+                    spread(0, 1, 5.0 / 16.0);
+                    spread(1, 1, 1.0 / 16.0);
+                    alive
+                }
+            };
+            if alive {
+                cells.push([x as i32, y as i32]);
+            }
+        }
+    }
+
+    Ok(Pattern::new(cells))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, ImageFormat, Luma};
+    use std::io::Cursor;
+
+    fn png(image: GrayImage) -> Vec<u8> {
+        let mut bytes = vec![];
+        image
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    fn seed(image: GrayImage, dithering: Dithering, invert: bool) -> Pattern {
+        let options = ImageSeedOptions {
+            size: [image.width(), image.height()],
+            threshold: 0.5,
+            dithering,
+            invert,
+        };
+        pattern_from_image(&png(image), &options).unwrap()
+    }
+
+    fn gray(width: u32, height: u32, value: u8) -> GrayImage {
+        GrayImage::from_pixel(width, height, Luma([value]))
+    }
+
+    #[test]
+    fn thresholds_dark_pixels_into_live_cells() {
+        let ramp = GrayImage::from_fn(4, 1, |x, _| Luma([[0, 100, 150, 255][x as usize]]));
+        assert_eq!(
+            seed(ramp.clone(), Dithering::Threshold, false),
+            Pattern::new([[0, 0], [1, 0]])
+        );
+        assert_eq!(
+            seed(ramp, Dithering::Threshold, true),
+            Pattern::new([[2, 0], [3, 0]])
+        );
+        assert_eq!(
+            seed(gray(4, 4, 128), Dithering::Threshold, false).population(),
+            0
+        );
+    }
+
+    #[test]
+    fn orders_mid_gray_by_the_bayer_matrix() {
+        let pattern = seed(gray(4, 4, 128), Dithering::Ordered, false);
+        let expected = (0..4)
+            .flat_map(|y| (0..4).map(move |x| [x, y]))
+            .filter(|&[x, y]| BAYER_4X4[y as usize][x as usize] >= 8.0);
+        assert_eq!(pattern, Pattern::new(expected));
+
+        assert_eq!(
+            seed(gray(4, 4, 0), Dithering::Ordered, false).population(),
+            16
+        );
+        assert_eq!(
+            seed(gray(4, 4, 255), Dithering::Ordered, false).population(),
+            0
+        );
+    }
+
+    #[test]
+    fn diffuses_mid_gray_into_half_the_cells() {
+        let pattern = seed(gray(8, 8, 128), Dithering::FloydSteinberg, false);
+        assert!((30..=34).contains(&pattern.population()));
+        // 첫 줄은 죽은 칸부터 번갈아 나온다
+        assert!(!pattern.cells().contains(&[0, 0]));
+        assert!(pattern.cells().contains(&[1, 0]));
+
+        assert_eq!(
+            seed(gray(8, 8, 0), Dithering::FloydSteinberg, false).population(),
+            64
+        );
+        assert_eq!(
+            seed(gray(8, 8, 255), Dithering::FloydSteinberg, false).population(),
+            0
+        );
+    }
+}
@@ -0,0 +1,251 @@
+use crate::pattern::{parse_rle, Pattern};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    StillLife,
+    Oscillator,
+    Spaceship,
+    Gun,
+    Methuselah,
+    Puffer,
+}
+
+impl Category {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::StillLife => "still life",
+            Category::Oscillator => "oscillator",
+            Category::Spaceship => "spaceship",
+            Category::Gun => "gun",
+            Category::Methuselah => "methuselah",
+            Category::Puffer => "puffer",
+        }
+    }
+}
+
+pub struct LibraryEntry {
+    pub name: &'static str,
+    pub category: Category,
+    /// `None` for patterns that never repeat
+    pub period: Option<u32>,
+    pub discovered: &'static str,
+    rle: &'static str,
+}
+
+impl LibraryEntry {
+    pub fn pattern(&self) -> Pattern {
+        parse_rle(self.rle).expect("built-in patterns are valid rle")
+    }
+}
+
+pub fn find_entry(name: &str) -> Option<usize> {
+    LIBRARY
+        .iter()
+        .position(|entry| entry.name.eq_ignore_ascii_case(name.trim()))
+}
+
+pub const DEFAULT_ENTRY: usize = 0;
+
+pub const LIBRARY: &[LibraryEntry] = &[
+    // 예전에 init_game 에 하드코딩 되어 있던 시작 패턴
+    LibraryEntry {
+        name: "Stacked bars",
+        category: Category::Methuselah,
+        period: None,
+        discovered: "",
+        rle: "4b4o2$2b8o2$12o2$2b8o2$4b4o!",
+    },
+    LibraryEntry {
+        name: "Block",
+        category: Category::StillLife,
+        period: Some(1),
+        discovered: "",
+        rle: "2o$2o!",
+    },
+    LibraryEntry {
+        name: "Beehive",
+        category: Category::StillLife,
+        period: Some(1),
+        discovered: "",
+        rle: "b2o$o2bo$b2o!",
+    },
+    LibraryEntry {
+        name: "Loaf",
+        category: Category::StillLife,
+        period: Some(1),
+        discovered: "",
+        rle: "b2o$o2bo$bobo$2bo!",
+    },
+    LibraryEntry {
+        name: "Boat",
+        category: Category::StillLife,
+        period: Some(1),
+        discovered: "",
+        rle: "2o$obo$bo!",
+    },
+    LibraryEntry {
+        name: "Pond",
+        category: Category::StillLife,
+        period: Some(1),
+        discovered: "",
+        rle: "b2o$o2bo$o2bo$b2o!",
+    },
+    LibraryEntry {
+        name: "Blinker",
+        category: Category::Oscillator,
+        period: Some(2),
+        discovered: "John Conway, 1969",
+        rle: "3o!",
+    },
+    LibraryEntry {
+        name: "Toad",
+        category: Category::Oscillator,
+        period: Some(2),
+        discovered: "Simon Norton, 1970",
+        rle: "b3o$3o!",
+    },
+    LibraryEntry {
+        name: "Beacon",
+        category: Category::Oscillator,
+        period: Some(2),
+        discovered: "John Conway, 1970",
+        rle: "2o$2o$2b2o$2b2o!",
+    },
+    LibraryEntry {
+        name: "Pulsar",
+        category: Category::Oscillator,
+        period: Some(3),
+        discovered: "John Conway, 1970",
+        rle: "2b3o3b3o2$o4bobo4bo$o4bobo4bo$o4bobo4bo$2b3o3b3o2$2b3o3b3o$o4bobo4bo$\
+              o4bobo4bo$o4bobo4bo2$2b3o3b3o!",
+    },
+    LibraryEntry {
+        name: "Pentadecathlon",
+        category: Category::Oscillator,
+        period: Some(15),
+        discovered: "John Conway, 1970",
+        rle: "2bo4bo$2ob4ob2o$2bo4bo!",
+    },
+    LibraryEntry {
+        name: "Glider",
+        category: Category::Spaceship,
+        period: Some(4),
+        discovered: "Richard K. Guy, 1969",
+        rle: "bo$2bo$3o!",
+    },
+    LibraryEntry {
+        name: "LWSS",
+        category: Category::Spaceship,
+        period: Some(4),
+        discovered: "John Conway, 1970",
+        rle: "bo2bo$o$o3bo$4o!",
+    },
+    LibraryEntry {
+        name: "MWSS",
+        category: Category::Spaceship,
+        period: Some(4),
+        discovered: "John Conway, 1970",
+        rle: "3bo$bo3bo$o$o4bo$5o!",
+    },
+    LibraryEntry {
+        name: "HWSS",
+        category: Category::Spaceship,
+        period: Some(4),
+        discovered: "John Conway, 1970",
+        rle: "3b2o$bo4bo$o$o5bo$6o!",
+    },
+    LibraryEntry {
+        name: "Gosper glider gun",
+        category: Category::Gun,
+        period: Some(30),
+        discovered: "Bill Gosper, 1970",
+        rle: "24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4bobo$\
+              10bo5bo7bo$11bo3bo$12b2o!",
+    },
+    LibraryEntry {
+        name: "R-pentomino",
+        category: Category::Methuselah,
+        period: None,
+        discovered: "John Conway, 1969",
+        rle: "b2o$2o$bo!",
+    },
+    LibraryEntry {
+        name: "Diehard",
+        category: Category::Methuselah,
+        period: None,
+        discovered: "",
+        rle: "6bo$2o$bo3b3o!",
+    },
+    LibraryEntry {
+        name: "Acorn",
+        category: Category::Methuselah,
+        period: None,
+        discovered: "Charles Corderman, 1971",
+        rle: "bo$3bo$2o2b3o!",
+    },
+    LibraryEntry {
+        name: "Pi-heptomino",
+        category: Category::Methuselah,
+        period: None,
+        discovered: "",
+        rle: "3o$obo$obo!",
+    },
+    LibraryEntry {
+        name: "Puffer train",
+        category: Category::Puffer,
+        period: Some(128),
+        discovered: "Bill Gosper, 1971",
+        rle: "3bo$4bo$o3bo$b4o4$o$b2o$2bo$2bo$bo3$3bo$4bo$o3bo$b4o!",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::{Periodicity, Rule};
+
+    fn population_after(pattern: &Pattern, generations: u32, rule: &Rule) -> usize {
+        (0..generations)
+            .fold(pattern.clone(), |pattern, _| pattern.step(rule))
+            .population()
+    }
+
+    #[test]
+    fn every_entry_repeats_as_catalogued() {
+        let rule = Rule::default();
+        assert_eq!(LIBRARY.len(), 21);
+        for entry in LIBRARY {
+            let pattern = entry.pattern();
+            let periodicity = pattern.periodicity(entry.period.unwrap_or(100), &rule);
+            match (entry.category, entry.period) {
+                (Category::StillLife, Some(1)) => {
+                    assert_eq!(periodicity, Some(Periodicity::StillLife), "{}", entry.name)
+                }
+                (Category::Oscillator, Some(period)) => assert_eq!(
+                    periodicity,
+                    Some(Periodicity::Oscillator { period }),
+                    "{}",
+                    entry.name
+                ),
+                (Category::Spaceship, Some(period)) => assert!(
+                    matches!(periodicity, Some(Periodicity::Spaceship { period: p, .. }) if p == period),
+                    "{}: {:?}",
+                    entry.name,
+                    periodicity
+                ),
+                // 총과 퍼퍼는 모양이 돌아오지 않고 주기마다 늘어난다
+                (Category::Gun | Category::Puffer, Some(period)) => {
+                    assert_eq!(periodicity, None, "{}", entry.name);
+                    assert!(
+                        population_after(&pattern, 2 * period, &rule)
+                            > population_after(&pattern, period, &rule),
+                        "{}",
+                        entry.name
+                    );
+                }
+                (Category::Methuselah, None) => assert_eq!(periodicity, None, "{}", entry.name),
+                _ => panic!("{} has no period for its category", entry.name),
+            }
+        }
+    }
+}
@@ -0,0 +1,185 @@
+pub use animation::{record_animation, AnimationFormat, AnimationOptions};
+pub use apgcode::{decode_apgcode, encode_apgcode};
+pub use census::{census, CensusEntry};
+pub use grid::Grid;
+pub use image_seed::{pattern_from_image, ImageSeedOptions};
+pub use library::{find_entry, Category, LibraryEntry, DEFAULT_ENTRY, LIBRARY};
+pub use rle::{encode_rle, parse_rle};
+pub use rule::{Boundary, Rule};
+pub use shape::{ellipse, flood_fill, line, rectangle};
+pub use snapshot::{encode_png, render_snapshot, SnapshotOptions};
+pub use soup::{parse_seed, random_soup, SoupOptions};
+pub use stamp::Stamp;
+
+mod animation;
+mod apgcode;
+mod census;
+mod grid;
+mod image_seed;
+mod library;
+mod rle;
+mod rule;
+mod shape;
+mod snapshot;
+mod soup;
+mod stamp;
+
+use std::collections::{HashMap, HashSet};
+
+/// A finite set of live cells.
+/// Coordinates follow the usual pattern-file convention: x to the right, y downward.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    cells: Vec<[i32; 2]>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipX,
+    FlipY,
+    Transpose,
+    AntiTranspose,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Periodicity {
+    StillLife,
+    Oscillator { period: u32 },
+    Spaceship { period: u32, displacement: [i32; 2] },
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 8] = [
+        Orientation::Identity,
+        Orientation::Rotate90,
+        Orientation::Rotate180,
+        Orientation::Rotate270,
+        Orientation::FlipX,
+        Orientation::FlipY,
+        Orientation::Transpose,
+        Orientation::AntiTranspose,
+    ];
+
+    pub fn apply(&self, [x, y]: [i32; 2]) -> [i32; 2] {
+        match self {
+            Orientation::Identity => [x, y],
+            Orientation::Rotate90 => [-y, x],
+            Orientation::Rotate180 => [-x, -y],
+            Orientation::Rotate270 => [y, -x],
+            Orientation::FlipX => [-x, y],
+            Orientation::FlipY => [x, -y],
+            Orientation::Transpose => [y, x],
+            Orientation::AntiTranspose => [-y, -x],
+        }
+    }
+}
+
+impl Pattern {
+    pub fn new<I: IntoIterator<Item = [i32; 2]>>(cells: I) -> Self {
+        let mut cells = cells.into_iter().collect::<Vec<_>>();
+        cells.sort_unstable_by_key(|&[x, y]| (y, x));
+        cells.dedup();
+        Pattern { cells }
+    }
+
+    pub fn cells(&self) -> &[[i32; 2]] {
+        &self.cells
+    }
+
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// `[x_min, y_min, x_max, y_max]`, inclusive.
+    pub fn bounding_box(&self) -> Option<[i32; 4]> {
+        let first = self.cells.first()?;
+        let mut bbox = [first[0], first[1], first[0], first[1]];
+        for &[x, y] in &self.cells {
+            bbox[0] = bbox[0].min(x);
+            bbox[1] = bbox[1].min(y);
+            bbox[2] = bbox[2].max(x);
+            bbox[3] = bbox[3].max(y);
+        }
+        Some(bbox)
+    }
+
+    pub fn translated(&self, dx: i32, dy: i32) -> Self {
+        Pattern {
+            cells: self.cells.iter().map(|&[x, y]| [x + dx, y + dy]).collect(),
+        }
+    }
+
+    /// Moves the pattern so that its bounding box starts at the origin.
+    pub fn normalized(&self) -> Self {
+        match self.bounding_box() {
+            Some(bbox) => self.translated(-bbox[0], -bbox[1]),
+            None => self.clone(),
+        }
+    }
+
+    pub fn oriented(&self, orientation: Orientation) -> Self {
+        Pattern::new(self.cells.iter().map(|&cell| orientation.apply(cell)))
+    }
+
+    /// Advances one generation of `rule` on an unbounded plane.
+    /// Rules with B0 are rejected when parsed, so dead cells away from the pattern stay dead.
+    pub fn step(&self, rule: &Rule) -> Self {
+        let alive = self.cells.iter().copied().collect::<HashSet<_>>();
+        // 이웃이 없는 살아있는 칸도 S0 이면 살아남으므로 후보에 넣는다
+        let mut neighbors = self
+            .cells
+            .iter()
+            .map(|&cell| (cell, 0))
+            .collect::<HashMap<[i32; 2], u32>>();
+        for &[x, y] in &self.cells {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    if dx != 0 || dy != 0 {
+                        *neighbors.entry([x + dx, y + dy]).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+
+        Pattern::new(
+            neighbors
+                .into_iter()
+                .filter(|(cell, count)| rule.next(alive.contains(cell), *count))
+                .map(|(cell, _)| cell),
+        )
+    }
+
+    /// Runs the pattern in isolation under `rule` until it returns to its starting shape.
+    pub fn periodicity(&self, max_period: u32, rule: &Rule) -> Option<Periodicity> {
+        let origin = self.bounding_box()?;
+        let shape = self.normalized();
+
+        let mut current = self.clone();
+        for period in 1..=max_period {
+            current = current.step(rule);
+            let Some(bbox) = current.bounding_box() else {
+                return None;
+            };
+            if current.normalized() != shape {
+                continue;
+            }
+
+            let displacement = [bbox[0] - origin[0], bbox[1] - origin[1]];
+            return Some(if displacement != [0, 0] {
+                Periodicity::Spaceship {
+                    period,
+                    displacement,
+                }
+            } else if period == 1 {
+                Periodicity::StillLife
+            } else {
+                Periodicity::Oscillator { period }
+            });
+        }
+        None
+    }
+}
@@ -0,0 +1,137 @@
+use anyhow::*;
+
+use crate::pattern::Pattern;
+
+/// Reads the run length encoded format used by most pattern collections.
+/// Comment lines and the `x = .., y = ..` header are skipped.
+pub fn parse_rle(rle: &str) -> Result<Pattern> {
+    let mut cells = vec![];
+    let mut position = [0, 0];
+    let mut run: Option<i32> = None;
+
+    let body = rle
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#') && !line.starts_with('x'));
+    'lines: for line in body {
+        for c in line.chars() {
+            match c {
+                '0'..='9' => {
+                    let digit = c.to_digit(10).unwrap() as i32;
+                    run = Some(run.unwrap_or(0) * 10 + digit);
+                    continue;
+                }
+                'b' | '.' => position[0] += run.unwrap_or(1),
+                '$' => {
+                    position[0] = 0;
+                    position[1] += run.unwrap_or(1);
+                }
+                '!' => break 'lines,
+                c if c.is_ascii_alphabetic() => {
+                    for _ in 0..run.unwrap_or(1) {
+                        cells.push(position);
+                        position[0] += 1;
+                    }
+                }
+                c if c.is_whitespace() => continue,
+                _ => bail!("unexpected character {} in rle", c),
+            }
+            run = None;
+        }
+    }
+
+    Ok(Pattern::new(cells))
+}
+
+/// Writes `pattern` as rle, starting from the top-left corner of its bounding box.
+pub fn encode_rle(pattern: &Pattern) -> String {
+    const LINE_WIDTH: usize = 70;
+
+    let Some(bbox) = pattern.bounding_box() else {
+        return "x = 0, y = 0\n!\n".to_string();
+    };
+    let mut rle = format!(
+        "x = {}, y = {}\n",
+        bbox[2] - bbox[0] + 1,
+        bbox[3] - bbox[1] + 1
+    );
+
+    // (run, tag) 목록을 만든 뒤 줄 길이에 맞춰 이어 붙인다
+    let mut runs: Vec<(i32, char)> = vec![];
+    let mut push = |run: i32, tag: char| match runs.last_mut() {
+        Some((last_run, last_tag)) if *last_tag == tag => *last_run += run,
+        _ => runs.push((run, tag)),
+    };
+    let mut position = [bbox[0], bbox[1]];
+    for &[x, y] in pattern.cells() {
+        if y > position[1] {
+            push(y - position[1], '$');
+            position = [bbox[0], y];
+        }
+        if x > position[0] {
+            push(x - position[0], 'b');
+        }
+        push(1, 'o');
+        position[0] = x + 1;
+    }
+    push(1, '!');
+
+    let mut line = String::new();
+    for (run, tag) in runs {
+        let token = match run {
+            1 => tag.to_string(),
+            _ => format!("{}{}", run, tag),
+        };
+        if line.len() + token.len() > LINE_WIDTH {
+            rle.push_str(&line);
+            rle.push('\n');
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    rle.push_str(&line);
+    rle.push('\n');
+    rle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_runs_over_several_lines() {
+        let rle = "#N two rows\n#C a comment\nx = 4, y = 3, rule = B3/S23\n2o2$\n4o\n!";
+        let pattern = parse_rle(rle).unwrap();
+        assert_eq!(
+            pattern,
+            Pattern::new([[0, 0], [1, 0], [0, 2], [1, 2], [2, 2], [3, 2]])
+        );
+    }
+
+    #[test]
+    fn stops_at_the_end_mark() {
+        let pattern = parse_rle("bo$2bo$3o!\nooo").unwrap();
+        assert_eq!(
+            pattern,
+            Pattern::new([[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]])
+        );
+        assert!(parse_rle("2o%o!").is_err());
+    }
+
+    #[test]
+    fn wraps_long_lines_and_reads_them_back() {
+        // 한 줄에 다 들어가지 않게 칸을 띄엄띄엄 놓는다
+        let pattern = Pattern::new((0..60).map(|x| [x * 2, x % 3 * 4]));
+        let rle = encode_rle(&pattern);
+        assert!(rle.lines().count() > 2);
+        assert!(rle.lines().all(|line| line.len() <= 70));
+        assert_eq!(parse_rle(&rle).unwrap(), pattern.normalized());
+    }
+
+    #[test]
+    fn encodes_runs_and_empty_rows() {
+        let pattern = Pattern::new([[0, 0], [1, 0], [2, 0], [0, 3], [4, 3]]);
+        assert_eq!(encode_rle(&pattern), "x = 5, y = 4\n3o3$o3bo!\n");
+        assert_eq!(encode_rle(&Pattern::default()), "x = 0, y = 0\n!\n");
+    }
+}
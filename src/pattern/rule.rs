@@ -0,0 +1,133 @@
+use anyhow::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Outer totalistic rule in B/S notation, e.g. `B3/S23`.
+/// Bit `n` of each mask is set when `n` live neighbors give birth or let a cell survive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    birth: u16,
+    survival: u16,
+}
+
+/// What the cells past the edge of the grid are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Boundary {
+    /// always dead
+    #[default]
+    Dead,
+    /// the opposite edge, so the grid wraps around
+    Torus,
+}
+
+impl Rule {
+    pub fn next(&self, alive: bool, neighbors: u32) -> bool {
+        let mask = if alive { self.survival } else { self.birth };
+        mask & (1 << neighbors) != 0
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule {
+            birth: 1 << 3,
+            survival: 1 << 2 | 1 << 3,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = |mask: u16| {
+            (0..=8)
+                .filter(|n| mask & (1 << n) != 0)
+                .map(|n| n.to_string())
+                .collect::<String>()
+        };
+        write!(f, "B{}/S{}", digits(self.birth), digits(self.survival))
+    }
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parse_mask = |part: &str, prefix: char| -> Result<u16> {
+            let Some(digits) = part
+                .strip_prefix(prefix)
+                .or_else(|| part.strip_prefix(prefix.to_ascii_lowercase()))
+            else {
+                bail!("rule {} should look like B3/S23", s);
+            };
+            digits.chars().try_fold(0, |mask, c| match c.to_digit(10) {
+                Some(n) if n <= 8 => Ok(mask | 1 << n),
+                _ => bail!("unexpected {} in rule {}", c, s),
+            })
+        };
+
+        let Some((birth, survival)) = s.trim().split_once('/') else {
+            bail!("rule {} should look like B3/S23", s);
+        };
+        let birth = parse_mask(birth, 'B')?;
+        if birth & 1 != 0 {
+            bail!("B0 rules are not supported");
+        }
+        Ok(Rule {
+            birth,
+            survival: parse_mask(survival, 'S')?,
+        })
+    }
+}
+
+impl Boundary {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Boundary::Dead => "dead",
+            Boundary::Torus => "torus",
+        }
+    }
+}
+
+impl FromStr for Boundary {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "dead" => Ok(Boundary::Dead),
+            "torus" => Ok(Boundary::Torus),
+            _ => bail!("unknown boundary {}", s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_prints_conway() {
+        let rule: Rule = "B3/S23".parse().unwrap();
+        assert_eq!(rule, Rule::default());
+        assert_eq!(rule.to_string(), "B3/S23");
+        assert!(rule.next(false, 3));
+        assert!(rule.next(true, 2));
+        assert!(!rule.next(false, 2));
+        assert!(!rule.next(true, 4));
+    }
+
+    #[test]
+    fn round_trips_other_rules() {
+        for text in ["B36/S23", "B2/S", "B3678/S34678", "B/S012345678"] {
+            assert_eq!(text.parse::<Rule>().unwrap().to_string(), text);
+        }
+        assert_eq!(" b36/s23 ".parse::<Rule>().unwrap().to_string(), "B36/S23");
+    }
+
+    #[test]
+    fn rejects_malformed_rules() {
+        assert!("B3S23".parse::<Rule>().is_err());
+        assert!("S23/B3".parse::<Rule>().is_err());
+        assert!("B39/S23".parse::<Rule>().is_err());
+        assert!("B03/S23".parse::<Rule>().is_err());
+    }
+}
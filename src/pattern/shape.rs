@@ -0,0 +1,179 @@
+use std::collections::{HashSet, VecDeque};
+
+/// Cells on the line from `from` to `to`, both ends included, without gaps or corner doubling.
+pub fn line(from: [i32; 2], to: [i32; 2]) -> Vec<[i32; 2]> {
+    // Bresenham, 모든 방향을 한 번에 다루는 정수 버전
+    let dx = (to[0] - from[0]).abs();
+    let dy = -(to[1] - from[1]).abs();
+    let step = [(to[0] - from[0]).signum(), (to[1] - from[1]).signum()];
+    let mut error = dx + dy;
+    let mut cell = from;
+    let mut cells = vec![cell];
+    while cell != to {
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            cell[0] += step[0];
+        }
+        if doubled <= dx {
+            error += dx;
+            cell[1] += step[1];
+        }
+        cells.push(cell);
+    }
+    cells
+}
+
+/// Cells of the rectangle spanned by two opposite corners, just the border unless `filled`.
+pub fn rectangle(from: [i32; 2], to: [i32; 2], filled: bool) -> Vec<[i32; 2]> {
+    let [x0, x1] = [from[0].min(to[0]), from[0].max(to[0])];
+    let [y0, y1] = [from[1].min(to[1]), from[1].max(to[1])];
+    (x0..=x1)
+        .flat_map(|x| (y0..=y1).map(move |y| [x, y]))
+        .filter(|&[x, y]| filled || x == x0 || x == x1 || y == y0 || y == y1)
+        .collect()
+}
+
+/// Cells of the ellipse fitting the rectangle spanned by two opposite corners,
+/// just the ring unless `filled`.
+pub fn ellipse(from: [i32; 2], to: [i32; 2], filled: bool) -> Vec<[i32; 2]> {
+    let [x0, x1] = [from[0].min(to[0]), from[0].max(to[0])];
+    let [y0, y1] = [from[1].min(to[1]), from[1].max(to[1])];
+    let center = [(x0 + x1) as f64 / 2.0, (y0 + y1) as f64 / 2.0];
+    // 칸의 바깥 가장자리까지 닿도록 반 칸씩 더한다
+    let radius = [(x1 - x0) as f64 / 2.0 + 0.5, (y1 - y0) as f64 / 2.0 + 0.5];
+    let inside = |[x, y]: [i32; 2]| {
+        let dx = (x as f64 - center[0]) / radius[0];
+        let dy = (y as f64 - center[1]) / radius[1];
+        dx * dx + dy * dy <= 1.0
+    };
+    (x0..=x1)
+        .flat_map(|x| (y0..=y1).map(move |y| [x, y]))
+        .filter(|&cell| inside(cell))
+        .filter(|&[x, y]| {
+            filled
+                || [[x - 1, y], [x + 1, y], [x, y - 1], [x, y + 1]]
+                    .iter()
+                    .any(|&neighbour| !inside(neighbour))
+        })
+        .collect()
+}
+
+/// Cells reachable from `start` through edge neighbours for which `matches` holds.
+/// `matches` has to turn false outside the grid, or the fill never ends.
+pub fn flood_fill(start: [i32; 2], matches: impl Fn([i32; 2]) -> bool) -> Vec<[i32; 2]> {
+    if !matches(start) {
+        return vec![];
+    }
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    let mut cells = vec![];
+    while let Some(cell @ [x, y]) = queue.pop_front() {
+        cells.push(cell);
+        for neighbour in [[x - 1, y], [x + 1, y], [x, y - 1], [x, y + 1]] {
+            if matches(neighbour) && visited.insert(neighbour) {
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut cells: Vec<[i32; 2]>) -> Vec<[i32; 2]> {
+        cells.sort();
+        cells
+    }
+
+    fn adjacent(a: [i32; 2], b: [i32; 2]) -> bool {
+        (a[0] - b[0]).abs() <= 1 && (a[1] - b[1]).abs() <= 1 && a != b
+    }
+
+    #[test]
+    fn lines_run_from_end_to_end_without_gaps() {
+        for (from, to) in [
+            ([0, 0], [0, 0]),
+            ([0, 0], [5, 0]),
+            ([0, 0], [0, -4]),
+            ([-3, 2], [4, -1]),
+            ([2, 7], [-1, -3]),
+        ] {
+            let cells = line(from, to);
+            assert_eq!(cells.first(), Some(&from));
+            assert_eq!(cells.last(), Some(&to));
+            let length = (to[0] - from[0]).abs().max((to[1] - from[1]).abs());
+            assert_eq!(cells.len(), length as usize + 1);
+            assert!(cells.windows(2).all(|pair| adjacent(pair[0], pair[1])));
+            // 반대 방향으로 그은 선은 시작점을 중심으로 정확히 뒤집힌다
+            let delta = [to[0] - from[0], to[1] - from[1]];
+            let flipped = line(from, [from[0] - delta[0], from[1] - delta[1]]);
+            assert_eq!(flipped.len(), cells.len());
+            assert!(cells
+                .iter()
+                .zip(&flipped)
+                .all(|(&[x, y], &[fx, fy])| [x + fx, y + fy] == [2 * from[0], 2 * from[1]]));
+        }
+    }
+
+    #[test]
+    fn rectangles_cover_the_border_or_the_whole_area() {
+        let border = rectangle([3, 2], [0, 0], false);
+        assert_eq!(border.len(), 10);
+        assert!(border
+            .iter()
+            .all(|&[x, y]| x == 0 || x == 3 || y == 0 || y == 2));
+        assert_eq!(
+            sorted(rectangle([0, 0], [3, 2], false)),
+            sorted(border.clone())
+        );
+
+        let filled = rectangle([0, 2], [3, 0], true);
+        assert_eq!(filled.len(), 12);
+        assert!(border.iter().all(|cell| filled.contains(cell)));
+        assert_eq!(rectangle([1, 1], [1, 1], false), vec![[1, 1]]);
+    }
+
+    #[test]
+    fn ellipses_are_symmetric_and_fit_their_box() {
+        for filled in [false, true] {
+            let cells = ellipse([-4, -2], [4, 2], filled);
+            for &[x, y] in &cells {
+                assert!((-4..=4).contains(&x) && (-2..=2).contains(&y));
+                assert!(cells.contains(&[-x, y]));
+                assert!(cells.contains(&[x, -y]));
+            }
+            // 상자의 네 변에 닿는다
+            for cell in [[-4, 0], [4, 0], [0, -2], [0, 2]] {
+                assert!(cells.contains(&cell));
+            }
+            assert_eq!(cells.contains(&[0, 0]), filled);
+        }
+        let ring = ellipse([-4, -2], [4, 2], false);
+        let disc = ellipse([-4, -2], [4, 2], true);
+        assert!(ring.iter().all(|cell| disc.contains(cell)));
+    }
+
+    #[test]
+    fn flood_fill_stays_inside_walls() {
+        // 3x3 안쪽을 둘러싼 5x5 벽
+        let wall = rectangle([0, 0], [4, 4], false);
+        let cells = flood_fill([2, 2], |cell @ [x, y]| {
+            (-10..=10).contains(&x) && (-10..=10).contains(&y) && !wall.contains(&cell)
+        });
+        assert_eq!(sorted(cells), sorted(rectangle([1, 1], [3, 3], true)));
+        assert!(flood_fill([0, 0], |cell| !wall.contains(&cell)).is_empty());
+    }
+
+    #[test]
+    fn flood_fill_of_an_open_region_stops_at_the_grid_bounds() {
+        let wall = line([-5, 0], [2, 0]);
+        let inside_grid = |[x, y]: [i32; 2]| (-5..=5).contains(&x) && (-5..=5).contains(&y);
+        let cells = flood_fill([0, -3], |cell| inside_grid(cell) && !wall.contains(&cell));
+        assert_eq!(cells.len(), 11 * 11 - wall.len());
+        assert!(cells.iter().all(|&cell| inside_grid(cell)));
+        assert!(cells.contains(&[0, 3]));
+    }
+}
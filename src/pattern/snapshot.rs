@@ -0,0 +1,157 @@
+use anyhow::*;
+use image::{ImageFormat, Rgba, RgbaImage};
+use std::collections::HashSet;
+use std::io::Cursor;
+
+use crate::pattern::Pattern;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    pub alive: [u8; 4],
+    pub dead: [u8; 4],
+    pub grid: [u8; 4],
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SnapshotOptions {
+    /// size of a single cell in pixels, not counting gridlines
+    pub cell_size: u32,
+    pub gridlines: bool,
+    pub palette: Palette,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette {
+            alive: [255, 255, 255, 255],
+            dead: [0, 0, 0, 255],
+            grid: [48, 48, 48, 255],
+        }
+    }
+}
+
+impl Default for SnapshotOptions {
+    fn default() -> Self {
+        SnapshotOptions {
+            cell_size: 8,
+            gridlines: true,
+            palette: Palette::default(),
+        }
+    }
+}
+
+/// Draws the cells of `pattern` inside `region` (`[x_min, y_min, x_max, y_max]`, inclusive).
+pub fn render_snapshot(
+    pattern: &Pattern,
+    region: [i32; 4],
+    options: &SnapshotOptions,
+) -> RgbaImage {
+    let columns = (region[2] - region[0] + 1).max(0) as u32;
+    let rows = (region[3] - region[1] + 1).max(0) as u32;
+    let line = options.gridlines as u32;
+    let pitch = options.cell_size + line;
+
+    let width = columns * pitch + line;
+    let height = rows * pitch + line;
+    let background = if options.gridlines {
+        options.palette.grid
+    } else {
+        options.palette.dead
+    };
+    let mut image = RgbaImage::from_pixel(width, height, Rgba(background));
+
+    let alive = pattern.cells().iter().copied().collect::<HashSet<_>>();
+    for row in 0..rows {
+        for column in 0..columns {
+            let cell = [region[0] + column as i32, region[1] + row as i32];
+            let color = if alive.contains(&cell) {
+                options.palette.alive
+            } else {
+                options.palette.dead
+            };
+
+            let left = column * pitch + line;
+            let top = row * pitch + line;
+            for y in top..top + options.cell_size {
+                for x in left..left + options.cell_size {
+                    image.put_pixel(x, y, Rgba(color));
+                }
+            }
+        }
+    }
+
+    image
+}
+
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>> {
+    let mut bytes = Cursor::new(vec![]);
+    image.write_to(&mut bytes, ImageFormat::Png)?;
+    Ok(bytes.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PALETTE: Palette = Palette {
+        alive: [1, 2, 3, 255],
+        dead: [4, 5, 6, 255],
+        grid: [7, 8, 9, 255],
+    };
+
+    fn options(gridlines: bool) -> SnapshotOptions {
+        SnapshotOptions {
+            cell_size: 4,
+            gridlines,
+            palette: PALETTE,
+        }
+    }
+
+    #[test]
+    fn sizes_the_image_from_the_cell_size() {
+        let pattern = Pattern::new([[0, 0]]);
+        let region = [-1, 0, 1, 1];
+        assert_eq!(
+            render_snapshot(&pattern, region, &options(true)).dimensions(),
+            (3 * 5 + 1, 2 * 5 + 1)
+        );
+        assert_eq!(
+            render_snapshot(&pattern, region, &options(false)).dimensions(),
+            (3 * 4, 2 * 4)
+        );
+        assert_eq!(
+            render_snapshot(&pattern, [0, 0, -1, -1], &options(false)).dimensions(),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn paints_cells_and_gridlines_in_the_palette() {
+        // 가운데 위 칸만 살아 있다
+        let pattern = Pattern::new([[0, 0], [5, 5]]);
+        let image = render_snapshot(&pattern, [-1, 0, 1, 1], &options(true));
+        let color = |x, y| image.get_pixel(x, y).0;
+        for x in 0..image.width() {
+            assert_eq!(color(x, 0), PALETTE.grid);
+            assert_eq!(color(x, 5), PALETTE.grid);
+        }
+        assert_eq!(color(5, 3), PALETTE.grid);
+        assert_eq!(color(6, 1), PALETTE.alive);
+        assert_eq!(color(9, 4), PALETTE.alive);
+        assert_eq!(color(1, 1), PALETTE.dead);
+        assert_eq!(color(6, 6), PALETTE.dead);
+
+        let image = render_snapshot(&pattern, [-1, 0, 1, 1], &options(false));
+        assert_eq!(image.get_pixel(0, 0).0, PALETTE.dead);
+        assert_eq!(image.get_pixel(4, 0).0, PALETTE.alive);
+        assert!(image.pixels().all(|pixel| pixel.0 != PALETTE.grid));
+    }
+
+    #[test]
+    fn encodes_a_png_that_decodes_back() {
+        let image = render_snapshot(&Pattern::new([[0, 0]]), [0, 0, 2, 2], &options(true));
+        let bytes = encode_png(&image).unwrap();
+        let decoded = image::load_from_memory(&bytes).unwrap().to_rgba8();
+        assert!(decoded == image);
+    }
+}
@@ -0,0 +1,211 @@
+use anyhow::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::str::FromStr;
+
+use crate::pattern::{Orientation, Pattern};
+
+/// Soup symmetries, named as in apgsearch.
+/// `_1` puts the center on a cell and `_4` on the corner between four cells.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    C1,
+    C2_1,
+    C2_4,
+    C4_1,
+    D2,
+    D4,
+    D8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoupOptions {
+    pub seed: u64,
+    /// chance for each cell to start alive
+    pub density: f32,
+    /// adjusted to the nearest size the symmetry allows
+    pub size: [u32; 2],
+    pub symmetry: Symmetry,
+}
+
+impl Symmetry {
+    fn orientations(&self) -> &'static [Orientation] {
+        use Orientation::*;
+        match self {
+            Symmetry::C1 => &[Identity],
+            Symmetry::C2_1 | Symmetry::C2_4 => &[Identity, Rotate180],
+            Symmetry::C4_1 => &[Identity, Rotate90, Rotate180, Rotate270],
+            Symmetry::D2 => &[Identity, FlipX],
+            Symmetry::D4 => &[Identity, FlipX, FlipY, Rotate180],
+            Symmetry::D8 => &Orientation::ALL,
+        }
+    }
+
+    fn size(&self, [width, height]: [u32; 2]) -> [u32; 2] {
+        let odd = |n: u32| n | 1;
+        let even = |n: u32| (n + 1) & !1;
+        match self {
+            Symmetry::C1 => [width, height],
+            Symmetry::C2_1 | Symmetry::D4 => [odd(width), odd(height)],
+            Symmetry::C2_4 => [even(width), even(height)],
+            Symmetry::D2 => [odd(width), height],
+            Symmetry::C4_1 | Symmetry::D8 => [odd(width.max(height)); 2],
+        }
+    }
+}
+
+impl FromStr for Symmetry {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "C1" => Ok(Symmetry::C1),
+            "C2_1" => Ok(Symmetry::C2_1),
+            "C2_4" => Ok(Symmetry::C2_4),
+            "C4_1" => Ok(Symmetry::C4_1),
+            "D2" => Ok(Symmetry::D2),
+            "D4" => Ok(Symmetry::D4),
+            "D8" => Ok(Symmetry::D8),
+            _ => bail!("unknown symmetry {}", s),
+        }
+    }
+}
+
+impl Default for SoupOptions {
+    fn default() -> Self {
+        SoupOptions {
+            seed: 0,
+            density: 0.5,
+            size: [16, 16],
+            symmetry: Symmetry::C1,
+        }
+    }
+}
+
+/// Numbers are used as they are, anything else is hashed so text seeds can be shared too.
+pub fn parse_seed(seed: &str) -> u64 {
+    let seed = seed.trim();
+    seed.parse().unwrap_or_else(|_| {
+        // FNV-1a, DefaultHasher 는 버전마다 결과가 달라질 수 있다
+        seed.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+    })
+}
+
+/// The same options always give the same soup, on every platform and version.
+pub fn random_soup(options: &SoupOptions) -> Pattern {
+    let [width, height] = options.symmetry.size(options.size);
+    let orientations = options.symmetry.orientations();
+    let mut rng = ChaCha8Rng::seed_from_u64(options.seed);
+
+    // 중심이 칸 위에 올 수도, 칸 사이에 올 수도 있어서 좌표를 두 배로 늘려서 다룬다
+    let to_doubled = |x: u32, y: u32| {
+        [
+            2 * x as i32 - (width as i32 - 1),
+            2 * y as i32 - (height as i32 - 1),
+        ]
+    };
+    let from_doubled = |[x, y]: [i32; 2]| [(x + width as i32 - 1) / 2, (y + height as i32 - 1) / 2];
+
+    let mut cells = vec![];
+    for y in 0..height {
+        for x in 0..width {
+            let cell = to_doubled(x, y);
+            let orbit = orientations
+                .iter()
+                .map(|orientation| orientation.apply(cell))
+                .collect::<Vec<_>>();
+            // 대칭으로 묶인 칸들 중 대표 한 칸에서만 주사위를 굴린다
+            let representative = orbit.iter().min_by_key(|&&[x, y]| (y, x)).unwrap();
+            if *representative != cell {
+                continue;
+            }
+            if rng.gen::<f32>() < options.density {
+                cells.extend(orbit.into_iter().map(from_doubled));
+            }
+        }
+    }
+
+    Pattern::new(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::encode_rle;
+    use std::collections::HashSet;
+
+    fn soup(symmetry: Symmetry, size: [u32; 2]) -> HashSet<[i32; 2]> {
+        let options = SoupOptions {
+            seed: 7,
+            size,
+            symmetry,
+            ..Default::default()
+        };
+        random_soup(&options).cells().iter().copied().collect()
+    }
+
+    #[test]
+    fn a_seed_always_gives_the_same_soup() {
+        let options = SoupOptions {
+            seed: parse_seed("life"),
+            size: [8, 8],
+            ..Default::default()
+        };
+        assert_eq!(
+            encode_rle(&random_soup(&options)),
+            "x = 8, y = 8\nobo2b2o$b2o3bo$2bo$4b2o$5bo$2obobo$bo$b2obob2o!\n"
+        );
+    }
+
+    #[test]
+    fn soups_have_their_symmetry() {
+        let rotate_180 = |[x, y]: [i32; 2], [w, h]: [i32; 2]| [w - 1 - x, h - 1 - y];
+        let rotate_90 = |[x, y]: [i32; 2], [w, _]: [i32; 2]| [w - 1 - y, x];
+        let flip_x = |[x, y]: [i32; 2], [w, _]: [i32; 2]| [w - 1 - x, y];
+        let flip_y = |[x, y]: [i32; 2], [_, h]: [i32; 2]| [x, h - 1 - y];
+        let transpose = |[x, y]: [i32; 2], _: [i32; 2]| [y, x];
+
+        type Map = fn([i32; 2], [i32; 2]) -> [i32; 2];
+        type Case<'a> = (Symmetry, [u32; 2], [i32; 2], &'a [Map]);
+        // 요청한 크기는 대칭이 허용하는 크기로 맞춰진다
+        let cases: [Case; 6] = [
+            (Symmetry::C2_1, [8, 6], [9, 7], &[rotate_180]),
+            (Symmetry::C2_4, [7, 5], [8, 6], &[rotate_180]),
+            (Symmetry::C4_1, [8, 5], [9, 9], &[rotate_90]),
+            (Symmetry::D2, [8, 6], [9, 6], &[flip_x]),
+            (Symmetry::D4, [8, 6], [9, 7], &[flip_x, flip_y]),
+            (
+                Symmetry::D8,
+                [9, 4],
+                [9, 9],
+                &[rotate_90, flip_x, transpose],
+            ),
+        ];
+        for (symmetry, requested, size, maps) in cases {
+            let cells = soup(symmetry, requested);
+            assert!(!cells.is_empty(), "{:?}", symmetry);
+            assert!(
+                cells
+                    .iter()
+                    .all(|&[x, y]| (0..size[0]).contains(&x) && (0..size[1]).contains(&y)),
+                "{:?} is not {:?}",
+                symmetry,
+                size
+            );
+            for map in maps {
+                let mapped = cells.iter().map(|&cell| map(cell, size)).collect();
+                assert_eq!(cells, mapped, "{:?}", symmetry);
+            }
+        }
+    }
+
+    #[test]
+    fn asymmetric_soups_are_not_mirrored() {
+        let cells = soup(Symmetry::C1, [16, 16]);
+        let flipped = cells.iter().map(|&[x, y]| [15 - x, y]).collect();
+        assert_ne!(cells, flipped);
+    }
+}
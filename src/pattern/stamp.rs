@@ -0,0 +1,45 @@
+use crate::pattern::{Orientation, Pattern};
+
+/// A pattern held under the cursor, waiting to be placed on the grid.
+/// Every rotation or reflection is applied to the held copy right away.
+#[derive(Clone, Debug)]
+pub struct Stamp {
+    pattern: Pattern,
+}
+
+impl Stamp {
+    pub fn new(pattern: &Pattern) -> Self {
+        Stamp {
+            pattern: pattern.normalized(),
+        }
+    }
+
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    /// Quarter turn, as seen on screen.
+    pub fn rotate(&mut self, clockwise: bool) {
+        // 패턴 좌표계는 y 가 아래쪽이라 Rotate90 이 화면에서는 시계 방향이다
+        let orientation = if clockwise {
+            Orientation::Rotate90
+        } else {
+            Orientation::Rotate270
+        };
+        self.orient(orientation);
+    }
+
+    /// Mirrors left and right.
+    pub fn flip_horizontal(&mut self) {
+        self.orient(Orientation::FlipX);
+    }
+
+    /// Mirrors top and bottom.
+    pub fn flip_vertical(&mut self) {
+        self.orient(Orientation::FlipY);
+    }
+
+    fn orient(&mut self, orientation: Orientation) {
+        self.pattern = self.pattern.oriented(orientation).normalized();
+    }
+}
@@ -1,275 +1,443 @@
+use crate::renderer::gpu_resource_manager::GPUResourceManager;
 use crate::renderer::mesh::InstanceColorTileRaw;
 use crate::renderer::objects::TextAttributes;
-use fontdue::Metrics;
-use std::cmp::{max, min};
+use std::cmp::max;
 use std::collections::HashMap;
 
-const RENDER_CHARACTER_ARRAY: [char; 64] = [
-    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
-    't', 'u', 'v', 'w', 'x', 'y', 'z', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L',
-    'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '0', '1', '2', '3', '4',
-    '5', '6', '7', '8', '9', ':', '.',
-];
+/// pixel size glyphs are rasterized at, whatever size the text is drawn
+const FONT_PIXEL_SIZE: f32 = 24.0;
+const PAGE_SIZE_MIN: u32 = 256;
+/// WebGL2 guarantees textures up to 2048 on a side
+const PAGE_SIZE_MAX: u32 = 2048;
+/// empty pixels around each glyph so sampling never picks up the neighbour
+const GLYPH_PADDING: u32 = 1;
 
-struct Glyph {
-    bitmap: Vec<u8>,
-    metrics: Metrics,
+#[derive(Clone, Copy)]
+struct GlyphSlot {
+    page: usize,
+    /// `[x, y, width, height]` in page pixels
+    rect: [u32; 4],
+    /// `[xmin, ymin]` from the pen position on the baseline to the bottom left of the bitmap
+    offset: [f32; 2],
+    advance: f32,
+    /// drawn as the fallback box because no loaded font has the character
+    missing: bool,
 }
 
-pub struct RasterizedFont {
-    glyphs: Vec<Glyph>,
-    size: [usize; 2],
-    max_y_min: i32,
+/// One atlas texture, filled shelf by shelf and doubled in size until `PAGE_SIZE_MAX`.
+struct AtlasPage {
+    size: u32,
+    pixels: Vec<u8>,
+    /// top left of the free space on the shelf being filled
+    cursor: [u32; 2],
+    shelf_height: u32,
+    /// where shelves start after growing, to fill the new column right of the old page first
+    column: u32,
+    /// pixels changed since the last upload
+    dirty: bool,
+    texture: Option<wgpu::Texture>,
 }
 
-struct FontRenderData {
-    uv: [f32; 4],
-    width: f32,
-    // offset : [f32;2]
-}
-
-#[derive(Default)]
+/// Glyph cache for the text pipeline.
+/// Characters are rasterized on first use, from the first font that has them.
 pub struct FontManager {
-    font_map: HashMap<char, FontRenderData>,
+    fonts: Vec<fontdue::Font>,
+    glyphs: HashMap<char, GlyphSlot>,
+    pages: Vec<AtlasPage>,
+    /// packed once and shared by every missing character
+    fallback_box: Option<GlyphSlot>,
+    /// `[ascent, descent]` of the bundled font in pixels, for placing every line the same
+    line_metrics: [f32; 2],
 }
 
-//todo fix WASM에서는 RGBA만 지원하는듯 하다.
-//Rgba8Unorm으로 텍스쳐 포멧 교체해야함
-impl FontManager {
-    pub fn font_rasterize(&mut self, font_size: f32) -> RasterizedFont {
+impl Default for FontManager {
+    fn default() -> Self {
         let font = include_bytes!("../../assets/font/Gameplay.ttf") as &[u8];
         let font = fontdue::Font::from_bytes(font, fontdue::FontSettings::default()).unwrap();
+        let line_metrics = font
+            .horizontal_line_metrics(FONT_PIXEL_SIZE)
+            .map_or([FONT_PIXEL_SIZE, 0.0], |metrics| {
+                [metrics.ascent, metrics.descent]
+            });
+        FontManager {
+            fonts: vec![font],
+            glyphs: HashMap::new(),
+            pages: vec![],
+            fallback_box: None,
+            line_metrics,
+        }
+    }
+}
 
-        let mut size = [0, 0];
-        let mut max_y_min = 0;
-        let mut glyphs = vec![];
+impl AtlasPage {
+    fn new() -> Self {
+        AtlasPage {
+            size: PAGE_SIZE_MIN,
+            pixels: vec![0; (PAGE_SIZE_MIN * PAGE_SIZE_MIN) as usize],
+            cursor: [0, 0],
+            shelf_height: 0,
+            column: 0,
+            dirty: true,
+            texture: None,
+        }
+    }
 
-        for character in RENDER_CHARACTER_ARRAY.iter() {
-            let (metrics, bitmap) = font.rasterize(*character, font_size);
-            glyphs.push(Glyph { bitmap, metrics });
-            size[0] = max(size[0], metrics.width);
-            size[1] = max(size[1], metrics.height);
-            max_y_min = min(max_y_min, metrics.ymin);
+    fn allocate(&mut self, [width, height]: [u32; 2]) -> Option<[u32; 2]> {
+        if self.cursor[0] + width > self.size {
+            self.cursor = [self.column, self.cursor[1] + self.shelf_height];
+            self.shelf_height = 0;
+        }
+        // 옛 페이지 높이만큼 오른쪽을 채웠으면 그 아래로 전체 폭을 쓴다
+        if self.column > 0 && self.cursor[1] + height > self.column {
+            self.cursor = [0, self.column];
+            self.shelf_height = 0;
+            self.column = 0;
+        }
+        if width > self.size || self.cursor[1] + height > self.size {
+            return None;
         }
+        let origin = self.cursor;
+        self.cursor[0] += width;
+        self.shelf_height = max(self.shelf_height, height);
+        Some(origin)
+    }
 
-        let char_in_row = 256 / size[0];
+    /// Doubles the page. Glyphs keep their pixel positions, only their uv shrinks.
+    fn grow(&mut self) {
+        let size = self.size * 2;
+        let mut pixels = vec![0; (size * size) as usize];
+        for (row, line) in self.pixels.chunks(self.size as usize).enumerate() {
+            let start = row * size as usize;
+            pixels[start..start + line.len()].copy_from_slice(line);
+        }
+        // 빈 페이지는 처음부터 다시 채운다
+        if self.cursor != [0, 0] {
+            self.cursor = [self.size, 0];
+            self.shelf_height = 0;
+            self.column = self.size;
+        }
+        self.size = size;
+        self.pixels = pixels;
+        self.dirty = true;
+    }
 
-        for (index, character) in RENDER_CHARACTER_ARRAY.iter().enumerate() {
-            let uv = [
-                (index % char_in_row) as f32 * size[0] as f32 * 0.00390625,
-                (index % char_in_row + 1) as f32 * size[0] as f32 * 0.00390625,
-                (index / char_in_row) as f32 * size[1] as f32 * 0.00390625,
-                (index / char_in_row + 1) as f32 * size[1] as f32 * 0.00390625,
-            ];
+    fn blit(&mut self, [x, y]: [u32; 2], width: u32, bitmap: &[u8]) {
+        if width == 0 {
+            return;
+        }
+        for (row, line) in bitmap.chunks(width as usize).enumerate() {
+            let start = ((y + row as u32) * self.size + x) as usize;
+            self.pixels[start..start + line.len()].copy_from_slice(line);
+        }
+        self.dirty = true;
+    }
+}
 
-            let metrics = glyphs[index].metrics;
+//todo fix WASM에서는 RGBA만 지원하는듯 하다.
+//Rgba8Unorm으로 텍스쳐 포멧 교체해야함
+impl FontManager {
+    /// Adds a font to fall back to for characters the ones before it don't have.
+    pub fn add_font(&mut self, bytes: &[u8]) -> anyhow::Result<()> {
+        let font = fontdue::Font::from_bytes(bytes, fontdue::FontSettings::default())
+            .map_err(anyhow::Error::msg)?;
+        self.fonts.push(font);
+        // 상자로 그리던 글자를 새 폰트로 다시 찾아본다
+        self.glyphs.retain(|_, glyph| !glyph.missing);
+        Ok(())
+    }
 
-            self.font_map.insert(
-                *character,
-                FontRenderData {
-                    uv,
-                    width: metrics.width as f32 / size[0] as f32,
-                },
-            );
+    fn glyph(&mut self, character: char) -> GlyphSlot {
+        if let Some(glyph) = self.glyphs.get(&character) {
+            return *glyph;
+        }
+        let glyph = match self
+            .fonts
+            .iter()
+            .find(|font| font.lookup_glyph_index(character) != 0)
+        {
+            Some(font) => {
+                let (metrics, bitmap) = font.rasterize(character, FONT_PIXEL_SIZE);
+                let rect = self.pack([metrics.width as u32, metrics.height as u32], &bitmap);
+                GlyphSlot {
+                    page: rect.0,
+                    rect: rect.1,
+                    offset: [metrics.xmin as f32, metrics.ymin as f32],
+                    advance: metrics.advance_width,
+                    missing: false,
+                }
+            }
+            None => self.missing_glyph(character.is_whitespace()),
+        };
+        self.glyphs.insert(character, glyph);
+        glyph
+    }
+
+    /// Outlined box, or nothing for whitespace, as wide as a narrow letter.
+    fn missing_glyph(&mut self, blank: bool) -> GlyphSlot {
+        if let Some(fallback_box) = self.fallback_box.filter(|_| !blank) {
+            return fallback_box;
+        }
+        let ascent = self.line_metrics[0];
+        let [width, height] = [(ascent * 0.5) as u32, (ascent * 0.8) as u32];
+        let (page, rect) = if blank {
+            self.pack([0, 0], &[])
+        } else {
+            let stroke = max(1, height / 12);
+            let bitmap = (0..height)
+                .flat_map(|y| (0..width).map(move |x| [x, y]))
+                .map(|[x, y]| {
+                    let edge =
+                        x < stroke || y < stroke || x + stroke >= width || y + stroke >= height;
+                    if edge {
+                        255
+                    } else {
+                        0
+                    }
+                })
+                .collect::<Vec<_>>();
+            self.pack([width, height], &bitmap)
+        };
+        let glyph = GlyphSlot {
+            page,
+            rect,
+            offset: [1.0, 0.0],
+            advance: width as f32 + 2.0,
+            missing: true,
+        };
+        if !blank {
+            self.fallback_box = Some(glyph);
+        }
+        glyph
+    }
+
+    /// Finds room on the last page, growing it or starting a new one when full.
+    fn pack(&mut self, [width, height]: [u32; 2], bitmap: &[u8]) -> (usize, [u32; 4]) {
+        if width == 0 || height == 0 {
+            return (0, [0, 0, 0, 0]);
+        }
+        let padded = [width + GLYPH_PADDING, height + GLYPH_PADDING];
+        if padded.iter().any(|&size| size > PAGE_SIZE_MAX) {
+            log::warn!("glyph of {}x{} is larger than an atlas page", width, height);
+            return (0, [0, 0, 0, 0]);
+        }
+        loop {
+            if self.pages.is_empty() {
+                self.pages.push(AtlasPage::new());
+            }
+            let index = self.pages.len() - 1;
+            let page = &mut self.pages[index];
+            if let Some(origin) = page.allocate(padded) {
+                page.blit(origin, width, bitmap);
+                return (index, [origin[0], origin[1], width, height]);
+            }
+            if page.size < PAGE_SIZE_MAX {
+                page.grow();
+            } else {
+                self.pages.push(AtlasPage::new());
+            }
         }
+    }
 
-        RasterizedFont {
-            glyphs,
-            size,
-            max_y_min,
+    /// Glyph instances for `texts`, one list per atlas page.
+    /// Pages touched here still have to go through `upload_pages` before they are drawn.
+    pub fn make_instances(&mut self, texts: &[TextAttributes]) -> Vec<Vec<InstanceColorTileRaw>> {
+        let mut result = vec![];
+        for text in texts {
+            for (page, instance) in self.make_instance_buffer(text) {
+                if result.len() <= page {
+                    result.resize_with(page + 1, Vec::new);
+                }
+                result[page].push(instance);
+            }
         }
+        // 글자가 하나도 안 남은 페이지도 비워야 하니 모든 페이지 몫을 돌려준다
+        result.resize_with(self.pages.len(), Vec::new);
+        result
     }
 
-    pub fn make_font_butter(
+    /// `text.position` is where the first line starts, at the left end of its vertical middle.
+    fn make_instance_buffer(
         &mut self,
-        rasterized_font: RasterizedFont,
-        output_buffer: wgpu::Buffer,
-        device: &wgpu::Device,
-        queue: &wgpu::Queue,
-    ) -> Result<wgpu::Buffer, wgpu::SurfaceError> {
-        //
-        let max_size = rasterized_font.size;
+        text: &TextAttributes,
+    ) -> Vec<(usize, InstanceColorTileRaw)> {
+        let [ascent, descent] = self.line_metrics;
+        let scale = text.size / (ascent - descent);
+        let line_space = text.size * 0.1;
+        let baseline = text.position[1] - text.size * 0.5 - descent * scale;
+        let mut result = Vec::new();
+        let mut pen = [text.position[0], baseline];
+        for character in text.content.chars() {
+            if character == '\n' {
+                pen[1] -= text.size + line_space;
+                pen[0] = text.position[0];
+                continue;
+            }
+
+            let glyph = self.glyph(character);
+            let [x, y, width, height] = glyph.rect;
+            if width > 0 && height > 0 {
+                let page_size = self.pages[glyph.page].size as f32;
+                let uv = [
+                    x as f32 / page_size,
+                    (x + width) as f32 / page_size,
+                    y as f32 / page_size,
+                    (y + height) as f32 / page_size,
+                ];
+                let size = [width as f32 * scale, height as f32 * scale];
+                let center = cgmath::Vector3 {
+                    x: pen[0] + glyph.offset[0] * scale + size[0] * 0.5,
+                    y: pen[1] + glyph.offset[1] * scale + size[1] * 0.5,
+                    z: text.position[2],
+                };
+                let translation_matrix = cgmath::Matrix4::from_translation(center);
+                let scale_matrix = cgmath::Matrix4::from_nonuniform_scale(size[0], size[1], 1.0);
+                let model = (translation_matrix * scale_matrix).into();
+                result.push((
+                    glyph.page,
+                    InstanceColorTileRaw {
+                        uv,
+                        model,
+                        color: text.color,
+                    },
+                ));
+            }
 
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("font buffer command encoder"),
-        });
+            pen[0] += glyph.advance * scale;
+        }
 
-        let char_in_row = 256 / max_size[0];
-        for (index, font_datum) in rasterized_font.glyphs.iter().enumerate() {
-            let metrics = font_datum.metrics;
-            let bitmap = &font_datum.bitmap;
+        result
+    }
 
+    /// Sends pages with new glyphs to the GPU, recreating the texture of any page that grew.
+    pub fn upload_pages(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        gpu_resource_manager: &mut GPUResourceManager,
+    ) {
+        for (index, page) in self.pages.iter_mut().enumerate() {
+            if !page.dirty {
+                continue;
+            }
             let size = wgpu::Extent3d {
-                width: metrics.width as u32,
-                height: metrics.height as u32,
+                width: page.size,
+                height: page.size,
                 depth_or_array_layers: 1,
             };
-            let texture = device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("single-font texture"),
-                size,
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::R8Unorm,
-                usage: wgpu::TextureUsages::COPY_SRC
-                    | wgpu::TextureUsages::TEXTURE_BINDING
-                    | wgpu::TextureUsages::COPY_DST,
-                view_formats: &[],
-            });
+            let recreate = page
+                .texture
+                .as_ref()
+                .map_or(true, |texture| texture.width() != page.size);
+            if recreate {
+                let texture = device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some("font_atlas"),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: wgpu::TextureFormat::R8Unorm,
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                    view_formats: &[],
+                });
+                gpu_resource_manager.set_font_page(device, index, &texture);
+                page.texture = Some(texture);
+            }
 
             queue.write_texture(
                 wgpu::ImageCopyTexture {
-                    texture: &texture,
+                    texture: page.texture.as_ref().unwrap(),
                     mip_level: 0,
                     origin: wgpu::Origin3d::ZERO,
                     aspect: wgpu::TextureAspect::All,
                 },
-                bitmap,
+                &page.pixels,
                 wgpu::ImageDataLayout {
                     offset: 0,
-                    bytes_per_row: Some(size.width),
-                    rows_per_image: Some(size.height),
-                },
-                size,
-            );
-
-            let offset = (index % char_in_row * max_size[0]
-                + index / char_in_row * 256 * max_size[1]
-                + metrics.xmin as usize
-                + 256
-                    * (max_size[1]
-                        - (metrics.height as i32 + metrics.ymin - rasterized_font.max_y_min)
-                            as usize)) as wgpu::BufferAddress;
-
-            encoder.copy_texture_to_buffer(
-                wgpu::ImageCopyTexture {
-                    aspect: wgpu::TextureAspect::All,
-                    texture: &texture,
-                    mip_level: 0,
-                    origin: wgpu::Origin3d::ZERO,
-                },
-                wgpu::ImageCopyBuffer {
-                    buffer: &output_buffer,
-                    layout: wgpu::ImageDataLayout {
-                        offset,
-                        bytes_per_row: Some(256),
-                        rows_per_image: Some(256),
-                    },
+                    bytes_per_row: Some(page.size),
+                    rows_per_image: Some(page.size),
                 },
                 size,
             );
+            page.dirty = false;
         }
-        queue.submit(Some(encoder.finish()));
-        Ok(output_buffer)
     }
 
-    pub async fn make_font_atlas_rgba(
-        &mut self,
-        device: &wgpu::Device,
-        queue: &wgpu::Queue,
-    ) -> Result<wgpu::Texture, wgpu::SurfaceError> {
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("font atlasing command encoder"),
-        });
-        let rasterized_font = self.font_rasterize(24.0);
+    /// Writes a page out as a grayscale image, for checking what got packed.
+    #[allow(unused)]
+    pub fn save_page(&self, page: usize, path: &str) -> anyhow::Result<()> {
+        let page = self
+            .pages
+            .get(page)
+            .ok_or_else(|| anyhow::anyhow!("no atlas page {}", page))?;
+        image::GrayImage::from_raw(page.size, page.size, page.pixels.clone())
+            .ok_or_else(|| anyhow::anyhow!("atlas page has the wrong size"))?
+            .save(path)?;
+        Ok(())
+    }
+}
 
-        let u8_size = std::mem::size_of::<u8>() as u32;
-        let output_buffer_size = (u8_size * 256 * 256) as wgpu::BufferAddress;
-        let output_buffer_desc = wgpu::BufferDescriptor {
-            size: output_buffer_size,
-            usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
-            label: Some("font atlas buffer"),
-            mapped_at_creation: false,
-        };
-        let output_buffer = device.create_buffer(&output_buffer_desc);
-        let output_buffer = self
-            .make_font_butter(rasterized_font, output_buffer, device, queue)
-            .unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        //region [ Make Font Atlas Texture ]
-        let size = wgpu::Extent3d {
-            width: 256,
-            height: 256,
-            depth_or_array_layers: 1,
-        };
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("font_atlas"),
-            size,
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::R8Unorm,
-            usage: wgpu::TextureUsages::COPY_SRC
-                | wgpu::TextureUsages::TEXTURE_BINDING
-                | wgpu::TextureUsages::COPY_DST
-                | wgpu::TextureUsages::RENDER_ATTACHMENT,
-            view_formats: &[],
-        });
+    #[test]
+    fn allocates_shelf_by_shelf() {
+        let mut page = AtlasPage::new();
+        assert_eq!(page.allocate([100, 10]), Some([0, 0]));
+        assert_eq!(page.allocate([100, 20]), Some([100, 0]));
+        // 남은 폭이 모자라면 가장 높았던 글자 아래로 내려간다
+        assert_eq!(page.allocate([100, 5]), Some([0, 20]));
+        assert_eq!(page.allocate([300, 5]), None);
+        assert_eq!(page.allocate([10, 300]), None);
+    }
 
-        encoder.copy_buffer_to_texture(
-            wgpu::ImageCopyBuffer {
-                buffer: &output_buffer,
-                layout: wgpu::ImageDataLayout {
-                    offset: 0,
-                    bytes_per_row: Some(256),
-                    rows_per_image: None,
-                },
-            },
-            wgpu::ImageCopyTexture {
-                texture: &texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-                aspect: Default::default(),
-            },
-            size,
-        );
-        queue.submit(Some(encoder.finish()));
-        //endregion
+    #[test]
+    fn grows_keeping_glyphs_in_place() {
+        let mut page = AtlasPage::new();
+        let origin = page.allocate([2, 2]).unwrap();
+        page.blit(origin, 2, &[1, 2, 3, 4]);
+        page.grow();
 
-        Ok(texture)
+        assert_eq!(page.size, PAGE_SIZE_MIN * 2);
+        let size = page.size as usize;
+        assert_eq!(page.pixels[..2], [1, 2]);
+        assert_eq!(page.pixels[size..size + 2], [3, 4]);
+        // 오른쪽에 새로 생긴 칸부터 채우고, 옛 페이지 높이를 넘으면 그 아래로 간다
+        assert_eq!(page.allocate([10, 10]), Some([256, 0]));
+        assert_eq!(page.allocate([256, 200]), Some([256, 10]));
+        assert_eq!(page.allocate([256, 100]), Some([0, 256]));
     }
 
-    fn get_render_data(&self, char_key: char) -> &FontRenderData {
-        match self.font_map.get(&char_key) {
-            Some(value) => value,
-            None => panic!("try to use unloaded font {}", char_key),
-        }
+    #[test]
+    fn starts_a_new_page_once_one_is_full() {
+        let mut font_manager = FontManager::default();
+        let bitmap = vec![255; 1500 * 1500];
+        let (first, _) = font_manager.pack([1500, 1500], &bitmap);
+        assert_eq!(first, 0);
+        assert_eq!(font_manager.pages[0].size, PAGE_SIZE_MAX);
+        let (second, rect) = font_manager.pack([1500, 1500], &bitmap);
+        assert_eq!((second, rect), (1, [0, 0, 1500, 1500]));
+        assert_eq!(font_manager.pages.len(), 2);
     }
 
-    pub fn make_instance_buffer(&self, text: &TextAttributes) -> Vec<InstanceColorTileRaw> {
-        let line_space = 0.1;
-        let mut result = Vec::new();
-        let mut position = cgmath::Vector3 {
-            x: text.position[0],
-            y: text.position[1],
-            z: text.position[2],
-        };
-        for txt in text.content.chars() {
-            if txt == ' ' {
-                position.x += text.size * 0.4;
-                continue;
-            }
-            if txt == '\n' {
-                position.y -= text.size + line_space;
-                position.x = text.position[0];
-                continue;
-            }
-
-            // let uppercase = txt.to_ascii_uppercase();
-            let render_data = self.get_render_data(txt);
-            let uv = render_data.uv;
-            let color = text.color;
-            let width = render_data.width;
+    #[test]
+    fn draws_missing_characters_as_one_shared_box() {
+        let mut font_manager = FontManager::default();
+        let first = font_manager.glyph('한');
+        let second = font_manager.glyph('글');
+        assert!(first.missing);
+        assert_eq!((first.page, first.rect), (second.page, second.rect));
 
-            let translation_matrix = cgmath::Matrix4::from_translation(position);
-            let scale_matrix =
-                cgmath::Matrix4::from_nonuniform_scale(text.size * 0.77, text.size, 1.0);
-            let model = (translation_matrix * scale_matrix).into();
-            result.push(InstanceColorTileRaw { uv, model, color });
+        let [x, y, width, height] = first.rect;
+        let page = &font_manager.pages[first.page];
+        let pixel = |px: u32, py: u32| page.pixels[((y + py) * page.size + x + px) as usize];
+        assert_eq!(pixel(0, 0), 255);
+        assert_eq!(pixel(width - 1, height - 1), 255);
+        assert_eq!(pixel(width / 2, height / 2), 0);
 
-            position.x += text.size * width * 0.77 + 0.03;
-        }
-
-        result
+        // 빈칸 문자는 상자 없이 자리만 차지한다
+        let blank = font_manager.glyph('\u{3000}');
+        assert!(blank.missing);
+        assert_eq!(blank.rect, [0, 0, 0, 0]);
     }
 }
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::default::Default;
+use std::ops::Range;
 use std::sync::Arc;
 
 use cgmath::SquareMatrix;
@@ -9,6 +10,7 @@ use wgpu::{BindGroup, BindGroupLayout, Buffer, Device, Queue, RenderPass};
 use crate::renderer::mesh::{InstanceTileRaw, Mesh};
 use crate::renderer::mesh_factory::make_tile_mesh;
 use crate::renderer::texture::TextureViewAndSampler;
+use crate::renderer::vertex::LineVertex;
 
 #[derive(Default)]
 pub struct GPUResourceManager {
@@ -16,12 +18,22 @@ pub struct GPUResourceManager {
     bind_groups: HashMap<String, HashMap<u32, Arc<BindGroup>>>,
     buffers: HashMap<String, Arc<Buffer>>,
     meshes_by_atlas: HashMap<String, Mesh>,
+    /// glyph atlas pages, each drawn as its own `font_<page>` mesh
+    font_pages: usize,
+    /// every overlay strip in one vertex buffer, drawn range by range
+    lines: Option<(Buffer, Vec<Range<u32>>)>,
 }
 
 impl GPUResourceManager {
-    pub fn initialize(&mut self, device: &Device) {
+    pub fn initialize(&mut self, device: &Device, aspect_ratio: f32) {
         self.init_base_layouts(device);
-        self.init_camera_bind_group(device);
+        self.init_matrix_bind_group(device, "camera", cgmath::Matrix4::identity());
+        // 화면 고정 UI 는 세로 -1..1, 가로는 비율만큼 늘린 좌표를 쓴다
+        self.init_matrix_bind_group(
+            device,
+            "screen",
+            cgmath::Matrix4::from_nonuniform_scale(1.0 / aspect_ratio, 1.0, 1.0),
+        );
     }
 
     pub fn init_atlas(&mut self, device: &Device, queue: &Queue) {
@@ -33,10 +45,20 @@ impl GPUResourceManager {
         )
         .unwrap();
         self.make_bind_group("agent", diffuse_texture, device);
+
+        let ghost_texture = TextureViewAndSampler::from_bytes(
+            device,
+            queue,
+            include_bytes!("../../assets/img/agent.png"),
+            "ghost",
+        )
+        .unwrap();
+        self.make_bind_group("ghost", ghost_texture, device);
     }
 
     pub fn init_meshes(&mut self, device: &Device) {
         self.add_mesh("agent", make_tile_mesh(device, "agent".to_string()));
+        self.add_mesh("ghost", make_tile_mesh(device, "ghost".to_string()));
         // self.add_mesh("bg", make_tile_mesh(device, "bg".to_string()));
         // self.add_mesh("player", make_tile_mesh(device, "player".to_string()));
     }
@@ -84,10 +106,16 @@ impl GPUResourceManager {
         );
     }
 
-    fn init_camera_bind_group(&mut self, device: &Device) {
-        let camera_uniform: [[f32; 4]; 4] = cgmath::Matrix4::identity().into();
+    /// Uniform matrix bound at group 0, with its buffer kept as `{name}_matrix`.
+    fn init_matrix_bind_group(
+        &mut self,
+        device: &Device,
+        name: &str,
+        matrix: cgmath::Matrix4<f32>,
+    ) {
+        let camera_uniform: [[f32; 4]; 4] = matrix.into();
         let camera_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Camera Buffer"),
+            label: Some(format!("{} Buffer", name).as_str()),
             contents: bytemuck::cast_slice(&[camera_uniform]),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
@@ -102,10 +130,10 @@ impl GPUResourceManager {
                 binding: 0,
                 resource: resources,
             }],
-            label: Some("camera_bind_group"),
+            label: Some(format!("{}_bind_group", name).as_str()),
         });
-        self.add_buffer("camera_matrix", camera_buffer);
-        self.add_bind_group("camera", 0, camera_bind_group);
+        self.add_buffer(format!("{}_matrix", name), camera_buffer);
+        self.add_bind_group(name, 0, camera_bind_group);
     }
 
     fn make_bind_group<T: Into<String> + Copy>(
@@ -305,17 +333,71 @@ impl GPUResourceManager {
         self.render_meshes(render_pass, "agent");
     }
 
-    pub fn init_ui_atlas(&mut self, device: &Device, font_texture: wgpu::Texture) {
-        let diffuse_texture =
-            TextureViewAndSampler::from_wgpu_texture(device, font_texture).unwrap();
-        self.make_bind_group("font", diffuse_texture, device);
+    /// Stamp preview, drawn with the translucent tile pipeline after the cells.
+    pub fn render_ghost<'a>(&'a self, render_pass: &mut RenderPass<'a>) {
+        self.render_meshes(render_pass, "ghost");
+    }
+
+    pub fn update_lines(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        vertices: Vec<LineVertex>,
+        ranges: Vec<Range<u32>>,
+    ) {
+        if vertices.is_empty() {
+            self.lines = None;
+            return;
+        }
+        let size = std::mem::size_of_val(vertices.as_slice()) as wgpu::BufferAddress;
+        match &mut self.lines {
+            Some((buffer, previous)) if buffer.size() == size => {
+                queue.write_buffer(buffer, 0, bytemuck::cast_slice(&vertices));
+                *previous = ranges;
+            }
+            _ => {
+                let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Line Vertex Buffer"),
+                    contents: bytemuck::cast_slice(&vertices),
+                    usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                });
+                self.lines = Some((buffer, ranges));
+            }
+        }
     }
 
-    pub fn init_ui_meshes(&mut self, device: &Device) {
-        self.add_mesh("font", make_tile_mesh(device, "font".to_string()));
+    /// Screen space overlay, drawn with the line strip pipeline.
+    pub fn render_lines<'a>(&'a self, render_pass: &mut RenderPass<'a>) {
+        let Some((buffer, ranges)) = &self.lines else {
+            return;
+        };
+        render_pass.set_vertex_buffer(0, buffer.slice(..));
+        for range in ranges {
+            render_pass.draw(range.clone(), 0..1);
+        }
     }
 
+    /// Binds a new or regrown glyph atlas page, adding its mesh the first time.
+    pub fn set_font_page(&mut self, device: &Device, page: usize, font_texture: &wgpu::Texture) {
+        let name = font_page_name(page);
+        let diffuse_texture =
+            TextureViewAndSampler::from_wgpu_texture(device, font_texture).unwrap();
+        self.make_bind_group(name.as_str(), diffuse_texture, device);
+        if !self.meshes_by_atlas.contains_key(&name) {
+            self.add_mesh(name.clone(), make_tile_mesh(device, name));
+        }
+        self.font_pages = self.font_pages.max(page + 1);
+    }
+
+    /// Text is laid out in screen units, y from -1 at the bottom to 1 at the top.
     pub fn render_ui<'a>(&'a self, render_pass: &mut RenderPass<'a>) {
-        self.render_meshes(render_pass, "font");
+        self.set_bind_group(render_pass, "screen");
+        for page in 0..self.font_pages {
+            self.render_meshes(render_pass, font_page_name(page));
+        }
     }
 }
+
+pub fn font_page_name(page: usize) -> String {
+    format!("font_{}", page)
+}
@@ -1,3 +1,4 @@
+pub use font_manager::FontManager;
 pub use objects::*;
 pub use render_context::RenderContext;
 pub use render_context_builder::*;
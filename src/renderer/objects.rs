@@ -7,6 +7,12 @@ pub struct TextAttributes {
     pub size: f32,
 }
 
+/// Connected line in screen space, `[-1, 1]` on both axes with y up, unaffected by the camera.
+pub struct LineStrip {
+    pub points: Vec<[f32; 2]>,
+    pub color: [f32; 4],
+}
+
 pub struct TileAttributes {
     pub uv: [f32; 4],
     pub position: [f32; 3],
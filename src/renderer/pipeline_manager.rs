@@ -3,10 +3,10 @@ use std::collections::HashMap;
 use crate::renderer::gpu_resource_manager::GPUResourceManager;
 use crate::renderer::mesh::{InstanceColorTileRaw, InstanceSimpleTileRaw};
 use crate::renderer::texture::TextureViewAndSampler;
-use crate::renderer::vertex::Vertex;
+use crate::renderer::vertex::{LineVertex, Vertex};
 use wgpu::{Device, Face, ShaderModule, TextureFormat, VertexBufferLayout};
 
-#[derive(Debug, Hash, Clone)]
+#[derive(Debug, Clone)]
 struct PipelineDesc<'a> {
     // pub shader: String,
     pub primitive_topology: wgpu::PrimitiveTopology,
@@ -21,6 +21,8 @@ struct PipelineDesc<'a> {
     pub cull_mode: Option<Face>,
     pub label: String,
     pub buffers: &'a [VertexBufferLayout<'a>],
+    /// values for the shader's `override` declarations
+    pub constants: HashMap<String, f64>,
     // pub depth_bias: i32,
 }
 
@@ -60,13 +62,19 @@ impl PipelineDesc<'_> {
             vertex: wgpu::VertexState {
                 module: &shader,
                 entry_point: "vs_main",
-                compilation_options: Default::default(),
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &self.constants,
+                    ..Default::default()
+                },
                 buffers: self.buffers,
             },
             fragment: Some(wgpu::FragmentState {
                 module: &shader,
                 entry_point: "fs_main",
-                compilation_options: Default::default(),
+                compilation_options: wgpu::PipelineCompilationOptions {
+                    constants: &self.constants,
+                    ..Default::default()
+                },
                 targets: &[Some(wgpu::ColorTargetState {
                     format: default_format,
                     blend: Some(wgpu::BlendState::ALPHA_BLENDING),
@@ -113,7 +121,7 @@ impl PipelineManager {
     ) {
         let shader =
             device.create_shader_module(wgpu::include_wgsl!("../../assets/shader/gradation.wgsl"));
-        let render_pipeline = PipelineDesc {
+        let tile_desc = PipelineDesc {
             primitive_topology: wgpu::PrimitiveTopology::TriangleList,
             depth_stencil: Some(wgpu::DepthStencilState {
                 format: TextureViewAndSampler::DEPTH_FORMAT,
@@ -134,11 +142,31 @@ impl PipelineManager {
             // cull_mode: Some(Face::Back),
             cull_mode: None,
             label: "Base Render Pipeline".to_string(),
-        }
-        .build(shader, device, default_format, gpu_resource_manager);
+            constants: HashMap::new(),
+        };
+        let ghost_desc = PipelineDesc {
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: TextureViewAndSampler::DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            label: "Ghost Render Pipeline".to_string(),
+            constants: HashMap::from([("alpha".to_string(), 0.35)]),
+            ..tile_desc.clone()
+        };
+        let render_pipeline = tile_desc.build(shader, device, default_format, gpu_resource_manager);
         self.pipelines
             .insert("tile_pl".to_string(), render_pipeline);
 
+        let shader =
+            device.create_shader_module(wgpu::include_wgsl!("../../assets/shader/gradation.wgsl"));
+        let render_pipeline =
+            ghost_desc.build(shader, device, default_format, gpu_resource_manager);
+        self.pipelines
+            .insert("ghost_pl".to_string(), render_pipeline);
+
         let shader =
             device.create_shader_module(wgpu::include_wgsl!("../../assets/shader/font.wgsl"));
         let render_pipeline = PipelineDesc {
@@ -161,11 +189,39 @@ impl PipelineManager {
             front_face: wgpu::FrontFace::Ccw,
             cull_mode: None,
             label: "Font Render Pipeline".to_string(),
+            constants: HashMap::new(),
         }
         .build(shader, device, default_format, gpu_resource_manager);
 
         self.pipelines
             .insert("font_pl".to_string(), render_pipeline);
+
+        let shader =
+            device.create_shader_module(wgpu::include_wgsl!("../../assets/shader/line.wgsl"));
+        let render_pipeline = PipelineDesc {
+            primitive_topology: wgpu::PrimitiveTopology::LineStrip,
+            // 오버레이는 셀 위에 항상 보이도록 깊이 검사를 하지 않는다
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: TextureViewAndSampler::DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            buffers: &[LineVertex::desc()],
+            sample_count: 1,
+            sampler_mask: 0,
+            alpha_to_coverage_enabled: false,
+            layouts: vec![],
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            label: "Line Render Pipeline".to_string(),
+            constants: HashMap::new(),
+        }
+        .build(shader, device, default_format, gpu_resource_manager);
+
+        self.pipelines
+            .insert("line_pl".to_string(), render_pipeline);
     }
 
     pub fn get_pipeline(&self, name: &str) -> &wgpu::RenderPipeline {
@@ -1,14 +1,14 @@
 use std::collections::HashMap;
 use std::iter;
 use std::sync::Arc;
-use wgpu::RequestAdapterOptions;
 use winit::dpi::PhysicalSize;
 use winit::window::Window;
 
 use crate::renderer::font_manager::FontManager;
-use crate::renderer::gpu_resource_manager::GPUResourceManager;
+use crate::renderer::gpu_resource_manager::{font_page_name, GPUResourceManager};
 use crate::renderer::pipeline_manager::PipelineManager;
-use crate::renderer::{texture, TextAttributes, TileAttributes};
+use crate::renderer::vertex::LineVertex;
+use crate::renderer::{texture, LineStrip, TextAttributes, TileAttributes};
 
 pub struct RenderContext {
     pub window: Arc<Window>,
@@ -35,19 +35,7 @@ impl RenderContext {
         self.gpu_resource_manager
             .init_atlas(&self.device, &self.queue);
         self.gpu_resource_manager.init_meshes(&self.device);
-
-        self.gpu_resource_manager.init_ui_meshes(&self.device);
-
-        // self.font_manager.test_font_atlas(&self.device, &self.queue).await.unwrap();
-
-        let font_texture = self
-            .font_manager
-            .make_font_atlas_rgba(&self.device, &self.queue)
-            .await
-            .unwrap();
-
-        self.gpu_resource_manager
-            .init_ui_atlas(&self.device, font_texture);
+        // 글자 atlas 는 처음 쓰일 때 FontManager 가 만든다
     }
 
     #[allow(unused)]
@@ -99,6 +87,15 @@ impl RenderContext {
             .write_buffer(&camera_buffer, 0, bytemuck::cast_slice(&[camera_uniform]));
     }
 
+    /// Stretches screen units to the window's aspect ratio, as `get_text_data` lays them out.
+    pub fn update_screen_buffer(&self, aspect_ratio: f32) {
+        let screen_buffer = self.gpu_resource_manager.get_buffer("screen_matrix");
+        let screen_uniform: [[f32; 4]; 4] =
+            cgmath::Matrix4::from_nonuniform_scale(1.0 / aspect_ratio, 1.0, 1.0).into();
+        self.queue
+            .write_buffer(&screen_buffer, 0, bytemuck::cast_slice(&[screen_uniform]));
+    }
+
     pub fn update_mesh_instance(&mut self, tile_render_data: HashMap<String, Vec<TileAttributes>>) {
         for pair in tile_render_data {
             let instance_data = (pair.1)
@@ -115,19 +112,34 @@ impl RenderContext {
         }
     }
 
-    #[allow(unused)]
     pub fn update_text_instance(&mut self, texts: Vec<TextAttributes>) {
-        let tile_instance = texts
-            .iter()
-            .flat_map(|text| self.font_manager.make_instance_buffer(text))
-            .collect::<Vec<_>>();
-
-        self.gpu_resource_manager.update_mesh_instance(
-            "font",
-            &self.device,
-            &self.queue,
-            tile_instance,
-        );
+        let pages = self.font_manager.make_instances(&texts);
+        self.font_manager
+            .upload_pages(&self.device, &self.queue, &mut self.gpu_resource_manager);
+
+        for (page, tile_instance) in pages.into_iter().enumerate() {
+            self.gpu_resource_manager.update_mesh_instance(
+                font_page_name(page),
+                &self.device,
+                &self.queue,
+                tile_instance,
+            );
+        }
+    }
+
+    pub fn update_line_strips(&mut self, strips: Vec<LineStrip>) {
+        let mut vertices = vec![];
+        let mut ranges = vec![];
+        for strip in strips.iter().filter(|strip| strip.points.len() > 1) {
+            let start = vertices.len() as u32;
+            vertices.extend(strip.points.iter().map(|&position| LineVertex {
+                position,
+                color: strip.color,
+            }));
+            ranges.push(start..vertices.len() as u32);
+        }
+        self.gpu_resource_manager
+            .update_lines(&self.device, &self.queue, vertices, ranges);
     }
 
     pub fn render(&self) -> Result<(), wgpu::SurfaceError> {
@@ -178,9 +190,17 @@ impl RenderContext {
             render_pass.set_pipeline(render_pipeline);
             self.gpu_resource_manager.render(&mut render_pass);
 
+            let render_pipeline = self.pipeline_manager.get_pipeline("ghost_pl");
+            render_pass.set_pipeline(render_pipeline);
+            self.gpu_resource_manager.render_ghost(&mut render_pass);
+
             let render_pipeline = self.pipeline_manager.get_pipeline("font_pl");
             render_pass.set_pipeline(render_pipeline);
             self.gpu_resource_manager.render_ui(&mut render_pass);
+
+            let render_pipeline = self.pipeline_manager.get_pipeline("line_pl");
+            render_pass.set_pipeline(render_pipeline);
+            self.gpu_resource_manager.render_lines(&mut render_pass);
         }
 
         self.queue.submit(iter::once(encoder.finish()));
@@ -190,60 +210,16 @@ impl RenderContext {
         Ok(())
     }
 
+    /// Saves the first glyph atlas page with the hud characters in it.
     #[allow(unused)]
-    pub async fn make_font_atlas() {
-        // The instance is a handle to our GPU
-        // BackendBit::PRIMARY => Vulkan + Metal + DX12 + Browser WebGPU
-        let instance = wgpu::Instance::default();
-
-        let adapter = instance
-            .request_adapter(&RequestAdapterOptions {
-                power_preference: wgpu::PowerPreference::default(),
-                force_fallback_adapter: false,
-                compatible_surface: None,
-            })
-            .await
-            .unwrap();
-
-        let (device, queue) = adapter
-            .request_device(&Default::default(), None)
-            .await
-            .unwrap();
-
+    pub fn make_font_atlas() {
         let mut font_manager = FontManager::default();
-
-        let rasterized_font = font_manager.font_rasterize(24.0);
-        let u8_size = std::mem::size_of::<u8>() as u32;
-        let output_buffer_size = (u8_size * 256 * 256 * 4) as wgpu::BufferAddress;
-        let output_buffer_desc = wgpu::BufferDescriptor {
-            size: output_buffer_size,
-            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
-            label: Some("font atlas buffer"),
-            mapped_at_creation: false,
-        };
-        let output_buffer = device.create_buffer(&output_buffer_desc);
-        let output_buffer = font_manager
-            .make_font_butter(rasterized_font, output_buffer, &device, &queue)
-            .unwrap();
-
-        {
-            let buffer_slice = output_buffer.slice(..);
-
-            // NOTE: We have to create the mapping THEN device.poll() before await
-            // the future. Otherwise, the application will freeze.
-            let (tx, rx) = futures_intrusive::channel::shared::oneshot_channel();
-            buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
-                tx.send(result).unwrap();
-            });
-            device.poll(wgpu::Maintain::Wait);
-            rx.receive().await.unwrap().unwrap();
-
-            let data = buffer_slice.get_mapped_range();
-
-            use image::{ImageBuffer, Rgba};
-            let buffer = ImageBuffer::<Rgba<u8>, _>::from_raw(256, 256, data).unwrap();
-            buffer.save("assets/img/font.png").unwrap();
-        }
-        output_buffer.unmap();
+        font_manager.make_instances(&[TextAttributes {
+            content: "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789:./-(),".into(),
+            color: [1.0, 1.0, 1.0],
+            position: [0.0, 0.0, 0.0],
+            size: 0.05,
+        }]);
+        font_manager.save_page(0, "assets/img/font.png").unwrap();
     }
 }
@@ -151,7 +151,7 @@ pub fn create_render_context(
 
         let mut gpu_resource_manager =
             crate::renderer::gpu_resource_manager::GPUResourceManager::default();
-        gpu_resource_manager.initialize(&device);
+        gpu_resource_manager.initialize(&device, aspect_ratio);
         let mut pipeline_manager = crate::renderer::pipeline_manager::PipelineManager::default();
         pipeline_manager.init_pipelines(&device, config.format, &gpu_resource_manager);
 
@@ -51,7 +51,7 @@ impl TextureViewAndSampler {
     }
 
     #[allow(unused)]
-    pub fn from_wgpu_texture(device: &wgpu::Device, texture: wgpu::Texture) -> Result<Self> {
+    pub fn from_wgpu_texture(device: &wgpu::Device, texture: &wgpu::Texture) -> Result<Self> {
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
             address_mode_u: wgpu::AddressMode::ClampToEdge,
@@ -131,6 +131,6 @@ impl TextureViewAndSampler {
             size,
         );
 
-        Self::from_wgpu_texture(device, texture)
+        Self::from_wgpu_texture(device, &texture)
     }
 }
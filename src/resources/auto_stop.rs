@@ -0,0 +1,43 @@
+use std::fmt;
+
+use super::Cycle;
+use crate::configs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    Extinct,
+    Periodic(Cycle),
+    GenerationLimit,
+}
+
+/// Conditions that pause the play loop on their own. All of them are off by default.
+#[derive(Default)]
+pub struct AutoStop {
+    pub on_extinction: bool,
+    /// pause once the grid repeats with at most this period
+    pub max_period: Option<u32>,
+    pub generation_limit: Option<u64>,
+    /// set by the `AutoStop` system, taken by `GameState` which does the pausing
+    pub fired: Option<(u64, StopReason)>,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopReason::Extinct => write!(f, "population reached zero"),
+            StopReason::Periodic(cycle) => write!(f, "became periodic, {}", cycle),
+            StopReason::GenerationLimit => write!(f, "generation limit"),
+        }
+    }
+}
+
+impl AutoStop {
+    /// Stops when the grid dies out or settles into a short cycle, what the toggle key turns on.
+    pub fn settled() -> Self {
+        AutoStop {
+            on_extinction: true,
+            max_period: Some(configs::AUTO_STOP_MAX_PERIOD),
+            ..Default::default()
+        }
+    }
+}
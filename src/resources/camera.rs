@@ -36,6 +36,8 @@ pub struct Camera {
     view: View,
     proj: cgmath::Matrix4<f32>,
     magic: [f32; 2],
+    /// physical pixels of the window, the unit of cursor and touch positions
+    screen_size: [f32; 2],
 }
 
 impl Default for Camera {
@@ -66,9 +68,25 @@ impl Camera {
             },
             proj,
             magic,
+            screen_size: SCREEN_SIZE.map(|size| size as f32),
         }
     }
 
+    pub fn screen_size(&self) -> [f32; 2] {
+        self.screen_size
+    }
+
+    /// Follows the window size, which on a high DPI screen is larger than `SCREEN_SIZE`.
+    pub fn set_screen_size(&mut self, screen_size: [f32; 2]) {
+        if screen_size[0] <= 0.0 || screen_size[1] <= 0.0 {
+            return;
+        }
+        // 한 픽셀이 움직이는 거리는 픽셀 수에 반비례한다
+        self.magic[0] *= self.screen_size[0] / screen_size[0];
+        self.magic[1] *= self.screen_size[1] / screen_size[1];
+        self.screen_size = screen_size;
+    }
+
     #[allow(unused)]
     pub fn move_to(&mut self, position: [f32; 3]) -> [f32; 3] {
         self.view.move_to(position)
@@ -100,9 +118,24 @@ impl Camera {
             },
             proj,
             magic,
+            screen_size: SCREEN_SIZE.map(|size| size as f32),
         }
     }
 
+    pub fn eye(&self) -> [f32; 3] {
+        self.view.eye.into()
+    }
+
+    pub fn target(&self) -> [f32; 3] {
+        self.view.target.into()
+    }
+
+    /// Puts the camera back exactly, e.g. when a session is loaded.
+    pub fn set_view(&mut self, eye: [f32; 3], target: [f32; 3]) {
+        self.view.eye = eye.into();
+        self.view.target = target.into();
+    }
+
     pub fn get_view_proj(&self) -> [[f32; 4]; 4] {
         let vp = self.build_view_projection_matrix();
         vp.into()
@@ -113,15 +146,39 @@ impl Camera {
         self.proj * view
     }
 
-    #[allow(unused)]
-    fn screen_to_world(&self, position: Point2<f32>) -> Point3<f32> {
-        let ndc_coords: cgmath::Vector4<f32> = [
-            (2.0 * position[0]) / SCREEN_SIZE[0] as f32 - 1.0,
-            1.0 - (2.0 * position[1]) / SCREEN_SIZE[1] as f32,
-            1.0,
-            1.0,
+    /// Point on the z = 0 plane, where the cells are, under the screen `position`.
+    pub fn screen_to_plane(&self, position: Point2<f32>) -> [f32; 2] {
+        // 원근 카메라라서 두 깊이로 ray 를 만들고 평면과 만나는 점을 구한다
+        let near = self.screen_to_world(position, 0.0);
+        let far = self.screen_to_world(position, 1.0);
+        let t = near.z / (near.z - far.z);
+        [near.x + (far.x - near.x) * t, near.y + (far.y - near.y) * t]
+    }
+
+    /// Cell under the screen `position`, in world cells.
+    pub fn screen_to_cell(&self, position: Point2<f32>) -> [i32; 2] {
+        let [x, y] = self.screen_to_plane(position);
+        [x.round() as i32, y.round() as i32]
+    }
+
+    /// Where a world point ends up on screen, in normalized device coordinates.
+    pub fn world_to_ndc(&self, position: [f32; 3]) -> [f32; 2] {
+        let clip = self.build_view_projection_matrix()
+            * cgmath::Vector4::new(position[0], position[1], position[2], 1.0);
+        [clip.x / clip.w, clip.y / clip.w]
+    }
+
+    /// Screen `position` in normalized device coordinates, where the overlay is drawn.
+    pub fn screen_to_ndc(&self, position: Point2<f32>) -> [f32; 2] {
+        [
+            (2.0 * position[0]) / self.screen_size[0] - 1.0,
+            1.0 - (2.0 * position[1]) / self.screen_size[1],
         ]
-        .into();
+    }
+
+    fn screen_to_world(&self, position: Point2<f32>, ndc_depth: f32) -> Point3<f32> {
+        let [x, y] = self.screen_to_ndc(position);
+        let ndc_coords: cgmath::Vector4<f32> = [x, y, ndc_depth, 1.0].into();
         let inv_proj = self.proj.invert().unwrap();
         let inv_view = cgmath::Matrix4::look_at_rh(self.view.eye, self.view.target, self.view.up)
             .invert()
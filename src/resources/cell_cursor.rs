@@ -0,0 +1,10 @@
+/// Cell picked with the keyboard, for editing without a mouse.
+#[derive(Default)]
+pub struct CellCursor {
+    /// hidden again once the mouse moves
+    pub cell: Option<[i32; 2]>,
+    /// seconds a cursor key has been held, for repeating the move
+    pub held_for: f32,
+    /// moves made since the key went down
+    pub moves: u32,
+}
@@ -0,0 +1,92 @@
+use std::fmt;
+
+use crate::configs;
+
+/// What the long-press menu offers, top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextAction {
+    /// copies the selection, or the object under the cell when the selection misses it
+    Copy,
+    /// places the clipboard centered on the cell
+    Paste,
+    /// places the selected library pattern centered on the cell
+    Stamp,
+    /// clears what `Copy` would copy
+    Clear,
+}
+
+/// Menu a long press opens at a cell. The next tap picks an item, or just closes it.
+#[derive(Default)]
+pub struct ContextMenu {
+    /// cell the menu acts on, `None` while it is closed
+    pub cell: Option<[i32; 2]>,
+    /// top left corner in normalized device coordinates
+    pub corner: [f32; 2],
+    /// set by `InterpretTouches`, taken by `Application` which runs the action
+    pub chosen: Option<(ContextAction, [i32; 2])>,
+}
+
+impl ContextAction {
+    pub const ALL: [ContextAction; 4] = [
+        ContextAction::Copy,
+        ContextAction::Paste,
+        ContextAction::Stamp,
+        ContextAction::Clear,
+    ];
+}
+
+impl fmt::Display for ContextAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextAction::Copy => write!(f, "Copy"),
+            ContextAction::Paste => write!(f, "Paste"),
+            ContextAction::Stamp => write!(f, "Stamp"),
+            ContextAction::Clear => write!(f, "Clear"),
+        }
+    }
+}
+
+impl ContextMenu {
+    /// Opens for `cell` with its top left corner at `anchor`, moved in to stay on screen.
+    pub fn open(&mut self, cell: [i32; 2], anchor: [f32; 2]) {
+        let height = configs::CONTEXT_MENU_ITEM_HEIGHT * ContextAction::ALL.len() as f32;
+        self.corner = [
+            anchor[0].min(1.0 - configs::CONTEXT_MENU_WIDTH),
+            anchor[1].max(-1.0 + height),
+        ];
+        self.cell = Some(cell);
+    }
+
+    /// Each item with its `[x0, y0, x1, y1]` in normalized device coordinates.
+    pub fn items(&self) -> impl Iterator<Item = (ContextAction, [f32; 4])> + '_ {
+        let [x, y] = self.corner;
+        ContextAction::ALL
+            .into_iter()
+            .enumerate()
+            .map(move |(row, action)| {
+                let top = y - configs::CONTEXT_MENU_ITEM_HEIGHT * row as f32;
+                (
+                    action,
+                    [
+                        x,
+                        top - configs::CONTEXT_MENU_ITEM_HEIGHT,
+                        x + configs::CONTEXT_MENU_WIDTH,
+                        top,
+                    ],
+                )
+            })
+    }
+
+    /// Closes the menu, choosing the item under `point` if there is one.
+    pub fn tap(&mut self, point: [f32; 2]) {
+        let Some(cell) = self.cell.take() else {
+            return;
+        };
+        let [x, y] = point;
+        let chosen = self
+            .items()
+            .find(|(_, [x0, y0, x1, y1])| (*x0..=*x1).contains(&x) && (*y0..=*y1).contains(&y))
+            .map(|(action, _)| (action, cell));
+        self.chosen = chosen;
+    }
+}
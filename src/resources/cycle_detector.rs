@@ -0,0 +1,229 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::configs;
+use crate::pattern::{Grid, Pattern};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cycle {
+    StillLife,
+    Oscillator {
+        period: u32,
+    },
+    /// `displacement` is in pattern coordinates, y pointing down
+    Moving {
+        period: u32,
+        displacement: [i32; 2],
+    },
+}
+
+/// Watches generations one at a time and notices when the live cells repeat.
+/// Only a hash and a position are kept per generation, for at most `capacity` generations,
+/// so cycles longer than that go unnoticed but memory stays flat.
+pub struct CycleDetector {
+    history: VecDeque<(u64, [i32; 2])>,
+    capacity: usize,
+    /// `[x_min, y_min, x_max, y_max]` in pattern coordinates, the whole grid when `None`
+    region: Option<[i32; 4]>,
+    /// the generation the current cycle was first noticed at
+    cycle: Option<(u64, Cycle)>,
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cycle::StillLife => write!(f, "still life"),
+            Cycle::Oscillator { period } => write!(f, "period {} oscillator", period),
+            Cycle::Moving {
+                period,
+                displacement,
+            } => write!(
+                f,
+                "moves by ({}, {}) every {} generations",
+                displacement[0], displacement[1], period
+            ),
+        }
+    }
+}
+
+impl Default for CycleDetector {
+    fn default() -> Self {
+        CycleDetector::new(configs::CYCLE_HISTORY)
+    }
+}
+
+impl CycleDetector {
+    pub fn new(capacity: usize) -> Self {
+        CycleDetector {
+            history: VecDeque::with_capacity(capacity),
+            capacity,
+            region: None,
+            cycle: None,
+        }
+    }
+
+    /// The cycle the latest generation is part of, if any, and the generation it was noticed at.
+    pub fn cycle(&self) -> Option<(u64, Cycle)> {
+        self.cycle
+    }
+
+    pub fn set_region(&mut self, region: Option<[i32; 4]>) {
+        self.region = region;
+        self.reset();
+    }
+
+    /// Forgets the history, for when the grid was changed by something other than a step.
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.cycle = None;
+    }
+
+    /// Feeds the next generation. Returns the cycle when one has just been entered.
+    pub fn observe(&mut self, generation: u64, grid: &Grid) -> Option<Cycle> {
+        let previous = self.cycle.map(|(_, cycle)| cycle);
+        let mut current = None;
+
+        let pattern = match self.region {
+            Some([x0, y0, x1, y1]) => Pattern::new(
+                grid.to_pattern()
+                    .cells()
+                    .iter()
+                    .copied()
+                    .filter(|&[x, y]| (x0..=x1).contains(&x) && (y0..=y1).contains(&y)),
+            ),
+            None => grid.to_pattern(),
+        };
+        // 다 죽은 상태는 주기라고 보지 않는다
+        let Some(bbox) = pattern.bounding_box() else {
+            self.history.clear();
+            self.cycle = None;
+            return None;
+        };
+
+        // 모양만 비교하려고 원점으로 옮긴 뒤 해시한다
+        let mut hasher = DefaultHasher::new();
+        pattern.normalized().hash(&mut hasher);
+        let hash = hasher.finish();
+        let origin = [bbox[0], bbox[1]];
+
+        let repeat = self
+            .history
+            .iter()
+            .rev()
+            .position(|(previous_hash, _)| *previous_hash == hash);
+        if let Some(index) = repeat {
+            let period = index as u32 + 1;
+            let (_, previous_origin) = self.history[self.history.len() - 1 - index];
+            let displacement = [
+                origin[0] - previous_origin[0],
+                origin[1] - previous_origin[1],
+            ];
+            current = Some(if displacement != [0, 0] {
+                Cycle::Moving {
+                    period,
+                    displacement,
+                }
+            } else if period == 1 {
+                Cycle::StillLife
+            } else {
+                Cycle::Oscillator { period }
+            });
+        }
+
+        if self.history.len() == self.capacity {
+            self.history.pop_front();
+        }
+        self.history.push_back((hash, origin));
+
+        match current {
+            Some(cycle) if previous != current => {
+                self.cycle = Some((generation, cycle));
+                Some(cycle)
+            }
+            Some(_) => None,
+            None => {
+                self.cycle = None;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::parse_rle;
+
+    fn grid_of(rle: &str) -> Grid {
+        let mut grid = Grid::new(32, 32);
+        for [x, y] in parse_rle(rle).unwrap().cells() {
+            grid.set(((x + 16) * 32 + 16 - y) as usize, true);
+        }
+        grid
+    }
+
+    /// Feeds `generations` steps of `grid` and returns what the last one reported.
+    fn watch(detector: &mut CycleDetector, mut grid: Grid, generations: u64) -> Option<Cycle> {
+        let mut found = None;
+        for generation in 0..=generations {
+            found = detector.observe(generation, &grid).or(found);
+            grid = grid.step();
+        }
+        found
+    }
+
+    #[test]
+    fn finds_still_lifes_and_oscillators() {
+        let mut detector = CycleDetector::default();
+        assert_eq!(
+            watch(&mut detector, grid_of("2o$2o!"), 3),
+            Some(Cycle::StillLife)
+        );
+        assert_eq!(detector.cycle(), Some((1, Cycle::StillLife)));
+
+        let mut detector = CycleDetector::default();
+        assert_eq!(
+            watch(&mut detector, grid_of("3o!"), 4),
+            Some(Cycle::Oscillator { period: 2 })
+        );
+        assert_eq!(detector.cycle(), Some((2, Cycle::Oscillator { period: 2 })));
+    }
+
+    #[test]
+    fn finds_a_glider_moving_diagonally() {
+        let mut detector = CycleDetector::default();
+        let glider = Cycle::Moving {
+            period: 4,
+            displacement: [1, 1],
+        };
+        assert_eq!(watch(&mut detector, grid_of("bo$2bo$3o!"), 8), Some(glider));
+        assert_eq!(detector.cycle(), Some((4, glider)));
+    }
+
+    #[test]
+    fn keeps_at_most_capacity_generations() {
+        let mut detector = CycleDetector::new(3);
+        assert_eq!(watch(&mut detector, grid_of("bo$2bo$3o!"), 8), None);
+        assert_eq!(detector.history.len(), 3);
+
+        let mut detector = CycleDetector::default();
+        watch(
+            &mut detector,
+            grid_of("2o$2o!"),
+            configs::CYCLE_HISTORY as u64 + 10,
+        );
+        assert_eq!(detector.history.len(), configs::CYCLE_HISTORY);
+        assert_eq!(detector.cycle(), Some((1, Cycle::StillLife)));
+    }
+
+    #[test]
+    fn forgets_cycles_once_everything_dies() {
+        let mut detector = CycleDetector::default();
+        watch(&mut detector, grid_of("2o$2o!"), 2);
+        assert_eq!(detector.observe(3, &Grid::new(32, 32)), None);
+        assert_eq!(detector.cycle(), None);
+        assert!(detector.history.is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Error, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tool {
+    Pencil,
+    Line,
+    Rectangle,
+    FilledRectangle,
+    Ellipse,
+    FilledEllipse,
+    FloodFill,
+}
+
+/// A shape finished with a drag, for `GameState` to draw through `edit_cells`.
+#[derive(Debug, Clone, Copy)]
+pub struct Drawn {
+    pub tool: Tool,
+    pub from: [i32; 2],
+    pub to: [i32; 2],
+    /// left drags draw live cells, right drags dead ones
+    pub alive: bool,
+}
+
+/// Tool used by mouse drags in the edit phase.
+pub struct DrawTool {
+    pub tool: Tool,
+    /// set while the game is in the edit phase
+    pub editing: bool,
+    /// cell the current drag started from, and whether it draws live cells
+    pub anchor: Option<([i32; 2], bool)>,
+    /// cell under the mouse during the drag
+    pub cursor: [i32; 2],
+    pub drawn: Option<Drawn>,
+}
+
+impl Tool {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tool::Pencil => "pencil",
+            Tool::Line => "line",
+            Tool::Rectangle => "rectangle",
+            Tool::FilledRectangle => "filled rectangle",
+            Tool::Ellipse => "ellipse",
+            Tool::FilledEllipse => "filled ellipse",
+            Tool::FloodFill => "flood fill",
+        }
+    }
+}
+
+/// The names `name` gives, e.g. "filled ellipse".
+impl FromStr for Tool {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pencil" => Ok(Tool::Pencil),
+            "line" => Ok(Tool::Line),
+            "rectangle" => Ok(Tool::Rectangle),
+            "filled rectangle" => Ok(Tool::FilledRectangle),
+            "ellipse" => Ok(Tool::Ellipse),
+            "filled ellipse" => Ok(Tool::FilledEllipse),
+            "flood fill" => Ok(Tool::FloodFill),
+            _ => bail!("unknown tool {}", s),
+        }
+    }
+}
+
+impl Default for DrawTool {
+    fn default() -> Self {
+        DrawTool {
+            tool: Tool::Pencil,
+            editing: false,
+            anchor: None,
+            cursor: [0, 0],
+            drawn: None,
+        }
+    }
+}
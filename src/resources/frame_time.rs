@@ -0,0 +1,6 @@
+/// Seconds since the systems last ran. Zero when they run again within the same frame
+/// to step more generations, so anything moving with time moves once per frame.
+#[derive(Default)]
+pub struct FrameTime {
+    pub dt: f32,
+}
@@ -0,0 +1,225 @@
+use std::collections::VecDeque;
+
+use crate::configs;
+use crate::pattern::Grid;
+
+/// One undoable change: the cells that flipped, by `Cell::index`, and the generation before and after.
+/// Flipping the same cells again goes back, so undo and redo share the diff.
+struct Change {
+    flips: Vec<u32>,
+    generation: [u64; 2],
+    /// a paint stroke keeps growing until the button is released
+    open: bool,
+}
+
+impl Change {
+    fn bytes(&self) -> usize {
+        std::mem::size_of::<Change>() + self.flips.len() * std::mem::size_of::<u32>()
+    }
+}
+
+/// Undo and redo stacks of grid diffs, dropping the oldest changes past `capacity` bytes.
+pub struct History {
+    undo: VecDeque<Change>,
+    redo: Vec<Change>,
+    bytes: usize,
+    capacity: usize,
+    /// the grid as of the latest change, to diff steps against
+    grid: Grid,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History::new(configs::HISTORY_BYTES)
+    }
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        History {
+            undo: VecDeque::new(),
+            redo: vec![],
+            bytes: 0,
+            capacity,
+            grid: Grid::default(),
+        }
+    }
+
+    /// Forgets every change and starts over from `grid`.
+    pub fn reset(&mut self, grid: Grid) {
+        self.undo.clear();
+        self.redo.clear();
+        self.bytes = 0;
+        self.grid = grid;
+    }
+
+    pub fn record_step(&mut self, generation: u64, grid: &Grid) {
+        self.record_grid(grid, [generation - 1, generation]);
+    }
+
+    /// Records whatever differs between the last known grid and `grid`, e.g. when a pattern is loaded.
+    pub fn record_grid(&mut self, grid: &Grid, generation: [u64; 2]) {
+        let flips = self
+            .grid
+            .cells()
+            .iter()
+            .zip(grid.cells())
+            .enumerate()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(index, _)| index as u32)
+            .collect();
+        self.grid = grid.clone();
+        self.push(Change {
+            flips,
+            generation,
+            open: false,
+        });
+    }
+
+    /// Records cells flipped by hand. Open edits are merged into the previous one while it is open too.
+    pub fn record_edit(&mut self, flips: Vec<u32>, generation: [u64; 2], open: bool) {
+        if flips.is_empty() && generation[0] == generation[1] {
+            return;
+        }
+        self.flip_mirror(&flips);
+
+        match self.undo.back_mut() {
+            Some(last) if open && last.open => {
+                self.bytes -= last.bytes();
+                // 같은 칸을 두 번 뒤집으면 원래대로라 짝을 지워 없앤다
+                last.flips.extend(flips);
+                last.flips.sort_unstable();
+                let mut merged: Vec<u32> = vec![];
+                for index in last.flips.drain(..) {
+                    if merged.last() == Some(&index) {
+                        merged.pop();
+                    } else {
+                        merged.push(index);
+                    }
+                }
+                last.flips = merged;
+                last.generation[1] = generation[1];
+                self.bytes += last.bytes();
+                self.redo.clear();
+            }
+            _ => self.push(Change {
+                flips,
+                generation,
+                open,
+            }),
+        }
+    }
+
+    /// Ends the current paint stroke so the next one gets its own undo step.
+    pub fn close_stroke(&mut self) {
+        if let Some(last) = self.undo.back_mut() {
+            last.open = false;
+        }
+    }
+
+    /// Cells to flip and the generation to go back to, if there is anything to undo.
+    pub fn undo(&mut self) -> Option<(Vec<u32>, u64)> {
+        let change = self.undo.pop_back()?;
+        self.bytes -= change.bytes();
+        self.flip_mirror(&change.flips);
+        let result = (change.flips.clone(), change.generation[0]);
+        self.redo.push(Change {
+            open: false,
+            ..change
+        });
+        Some(result)
+    }
+
+    pub fn redo(&mut self) -> Option<(Vec<u32>, u64)> {
+        let change = self.redo.pop()?;
+        self.flip_mirror(&change.flips);
+        let result = (change.flips.clone(), change.generation[1]);
+        self.bytes += change.bytes();
+        self.undo.push_back(change);
+        Some(result)
+    }
+
+    fn flip_mirror(&mut self, flips: &[u32]) {
+        for &index in flips {
+            let alive = self.grid.cells()[index as usize];
+            self.grid.set(index as usize, !alive);
+        }
+    }
+
+    fn push(&mut self, change: Change) {
+        self.redo.clear();
+        self.bytes += change.bytes();
+        self.undo.push_back(change);
+        while self.bytes > self.capacity {
+            let Some(oldest) = self.undo.pop_front() else {
+                break;
+            };
+            self.bytes -= oldest.bytes();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_and_redo_return_the_same_flips() {
+        let mut history = History::new(usize::MAX);
+        history.reset(Grid::new(4, 4));
+        history.record_edit(vec![1, 2], [5, 5], false);
+        history.record_edit(vec![3], [5, 5], false);
+
+        assert_eq!(history.undo(), Some((vec![3], 5)));
+        assert_eq!(history.undo(), Some((vec![1, 2], 5)));
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.redo(), Some((vec![1, 2], 5)));
+        assert_eq!(history.redo(), Some((vec![3], 5)));
+        assert_eq!(history.redo(), None);
+    }
+
+    #[test]
+    fn steps_are_diffed_against_the_last_grid() {
+        let mut history = History::new(usize::MAX);
+        history.reset(Grid::new(4, 4));
+        let mut grid = Grid::new(4, 4);
+        grid.set(0, true);
+        grid.set(6, true);
+        history.record_step(1, &grid);
+        history.record_edit(vec![6], [1, 1], false);
+        grid.set(6, false);
+        grid.set(7, true);
+        history.record_step(2, &grid);
+
+        assert_eq!(history.undo(), Some((vec![7], 1)));
+        assert_eq!(history.undo(), Some((vec![6], 1)));
+        assert_eq!(history.undo(), Some((vec![0, 6], 0)));
+        assert_eq!(history.redo(), Some((vec![0, 6], 1)));
+    }
+
+    #[test]
+    fn an_open_stroke_is_one_change() {
+        let mut history = History::new(usize::MAX);
+        history.reset(Grid::new(4, 4));
+        history.record_edit(vec![1], [0, 0], true);
+        history.record_edit(vec![2], [0, 0], true);
+        // 같은 칸을 다시 뒤집으면 짝이 없어진다
+        history.record_edit(vec![1, 3], [0, 0], true);
+        history.close_stroke();
+        history.record_edit(vec![4], [0, 0], true);
+
+        assert_eq!(history.undo(), Some((vec![4], 0)));
+        assert_eq!(history.undo(), Some((vec![2, 3], 0)));
+        assert_eq!(history.undo(), None);
+    }
+
+    #[test]
+    fn a_new_change_clears_redo() {
+        let mut history = History::new(usize::MAX);
+        history.reset(Grid::new(4, 4));
+        history.record_edit(vec![1], [0, 0], false);
+        history.undo();
+        history.record_edit(vec![2], [0, 0], false);
+        assert_eq!(history.redo(), None);
+    }
+}
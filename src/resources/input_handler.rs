@@ -1,7 +1,12 @@
+use std::collections::{BTreeMap, HashSet};
+
 use cgmath::{EuclideanSpace, Point2, Vector2, Zero};
+use winit::event::TouchPhase;
+use winit::keyboard::{KeyCode, ModifiersState};
 // use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
 
 bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct MouseButtons: u8 {
         const NONE = 0b00000000;
         const LEFT = 0b00000001;
@@ -15,6 +20,14 @@ pub struct InputHandler {
     pub mouse_delta: Vector2<f32>,
     pub mouse_wheel_delta: Vector2<f32>,
     pub mouse_pressed: MouseButtons,
+    /// buttons held on behalf of the fingers, kept apart so a gesture never releases a real button
+    touch_pressed: MouseButtons,
+    keys_held: HashSet<KeyCode>,
+    /// keys that went down since the systems last ran
+    keys_pressed: HashSet<KeyCode>,
+    pub modifiers: ModifiersState,
+    /// fingers on the screen by touch id
+    touches: BTreeMap<u64, Point2<f32>>,
 }
 
 impl Default for InputHandler {
@@ -24,18 +37,56 @@ impl Default for InputHandler {
             mouse_delta: Vector2::zero(),
             mouse_wheel_delta: Vector2::zero(),
             mouse_pressed: MouseButtons::empty(),
+            touch_pressed: MouseButtons::empty(),
+            keys_held: HashSet::new(),
+            keys_pressed: HashSet::new(),
+            modifiers: ModifiersState::empty(),
+            touches: BTreeMap::new(),
         }
     }
 }
 
 impl InputHandler {
     pub fn is_button_pressed(&self, button: MouseButtons) -> bool {
-        self.mouse_pressed.contains(button)
+        self.mouse_pressed
+            .union(self.touch_pressed)
+            .contains(button)
+    }
+
+    pub fn mouse_position(&self) -> Point2<f32> {
+        self.mouse_position_prev
     }
 
     pub fn reset_delta(&mut self) {
         self.mouse_wheel_delta = Vector2::zero();
         self.mouse_delta = Vector2::zero();
+        self.keys_pressed.clear();
+    }
+
+    pub fn is_key_held(&self, key: KeyCode) -> bool {
+        self.keys_held.contains(&key)
+    }
+
+    pub fn is_key_pressed(&self, key: KeyCode) -> bool {
+        self.keys_pressed.contains(&key)
+    }
+
+    pub fn key_input(&mut self, pressed: bool, key: KeyCode) {
+        if pressed {
+            if self.keys_held.insert(key) {
+                self.keys_pressed.insert(key);
+            }
+        } else {
+            self.keys_held.remove(&key);
+        }
+    }
+
+    /// Forgets held keys and touches, whose release goes unseen once the window loses focus.
+    pub fn release_keys(&mut self) {
+        self.keys_held.clear();
+        self.touches.clear();
+        self.touch_pressed = MouseButtons::empty();
+        self.modifiers = ModifiersState::empty();
     }
 
     pub fn cursor_moved(&mut self, position: Point2<f32>) {
@@ -43,6 +94,32 @@ impl InputHandler {
         self.mouse_position_prev = position;
     }
 
+    /// Moves the pointer without counting it as a movement, e.g. when a finger lands somewhere else.
+    pub fn warp_cursor(&mut self, position: Point2<f32>) {
+        self.mouse_position_prev = position;
+    }
+
+    pub fn touches(&self) -> impl Iterator<Item = Point2<f32>> + '_ {
+        self.touches.values().copied()
+    }
+
+    pub fn touch_input(&mut self, id: u64, phase: TouchPhase, position: Point2<f32>) {
+        match phase {
+            TouchPhase::Started => {
+                self.touches.insert(id, position);
+            }
+            // 도장을 찍느라 넘겨받지 않은 손가락은 무시한다
+            TouchPhase::Moved => {
+                if let Some(touch) = self.touches.get_mut(&id) {
+                    *touch = position;
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.touches.remove(&id);
+            }
+        }
+    }
+
     pub fn mouse_wheel(&mut self, delta: Vector2<f32>) {
         self.mouse_wheel_delta = delta;
     }
@@ -54,4 +131,34 @@ impl InputHandler {
             self.mouse_pressed.remove(button);
         }
     }
+
+    /// Buttons the current touch gesture stands for, replacing the ones it held before.
+    pub fn touch_buttons(&mut self, buttons: MouseButtons) {
+        self.touch_pressed = buttons;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn touches_never_release_mouse_buttons() {
+        let mut input_handler = InputHandler::default();
+        input_handler.mouse_input(true, MouseButtons::LEFT);
+        input_handler.touch_buttons(MouseButtons::LEFT);
+        input_handler.touch_buttons(MouseButtons::MIDDLE);
+        assert!(input_handler.is_button_pressed(MouseButtons::LEFT));
+        assert!(input_handler.is_button_pressed(MouseButtons::MIDDLE));
+
+        input_handler.touch_buttons(MouseButtons::empty());
+        assert!(input_handler.is_button_pressed(MouseButtons::LEFT));
+        assert!(!input_handler.is_button_pressed(MouseButtons::MIDDLE));
+
+        input_handler.mouse_input(false, MouseButtons::LEFT);
+        input_handler.touch_buttons(MouseButtons::RIGHT);
+        input_handler.release_keys();
+        assert!(!input_handler.is_button_pressed(MouseButtons::LEFT));
+        assert!(!input_handler.is_button_pressed(MouseButtons::RIGHT));
+    }
 }
@@ -0,0 +1,494 @@
+use std::collections::HashMap;
+
+use anyhow::*;
+use winit::keyboard::{KeyCode, ModifiersState};
+
+use crate::resources::{InputHandler, Tool};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Play,
+    Pause,
+    TogglePlay,
+    Step,
+    StepMany,
+    Slower,
+    Faster,
+    Reset,
+    Undo,
+    Redo,
+    Copy,
+    Cut,
+    Paste,
+    FillRandom,
+    Invert,
+    ClearSelected,
+    ScrubBack,
+    ScrubBackMany,
+    ScrubForward,
+    ScrubForwardMany,
+    SelectTool,
+    Edit,
+    DrawTool(Tool),
+    MirrorMode,
+    MirrorCenter,
+    Stamp,
+    Cancel,
+    RotateLeft,
+    RotateRight,
+    FlipHorizontal,
+    FlipVertical,
+    Soup,
+    Census,
+    AutoStop,
+    NextPattern,
+    PreviousPattern,
+    Snapshot,
+    SaveSession,
+    Graph,
+    SaveStatistics,
+    RecordGif,
+    RecordApng,
+    Apgcode,
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    ZoomIn,
+    ZoomOut,
+    CursorLeft,
+    CursorRight,
+    CursorUp,
+    CursorDown,
+    ToggleCell,
+}
+
+/// A key together with the modifiers that have to be held with it.
+/// Ctrl and the command key count as the same modifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub key: KeyCode,
+    pub modifiers: ModifiersState,
+}
+
+/// Which keys trigger which action. Loaded from a `action = Key, Ctrl+Key` text file
+/// over the defaults, so a file only needs the actions it changes.
+pub struct KeyMap {
+    bindings: HashMap<Action, Vec<KeyBinding>>,
+}
+
+const ACTION_NAMES: &[(&str, Action)] = &[
+    ("play", Action::Play),
+    ("pause", Action::Pause),
+    ("toggle_play", Action::TogglePlay),
+    ("step", Action::Step),
+    ("step_10", Action::StepMany),
+    ("slower", Action::Slower),
+    ("faster", Action::Faster),
+    ("reset", Action::Reset),
+    ("undo", Action::Undo),
+    ("redo", Action::Redo),
+    ("copy", Action::Copy),
+    ("cut", Action::Cut),
+    ("paste", Action::Paste),
+    ("fill_random", Action::FillRandom),
+    ("invert", Action::Invert),
+    ("clear_selected", Action::ClearSelected),
+    ("scrub_back", Action::ScrubBack),
+    ("scrub_back_10", Action::ScrubBackMany),
+    ("scrub_forward", Action::ScrubForward),
+    ("scrub_forward_10", Action::ScrubForwardMany),
+    ("select_tool", Action::SelectTool),
+    ("edit", Action::Edit),
+    ("pencil", Action::DrawTool(Tool::Pencil)),
+    ("line", Action::DrawTool(Tool::Line)),
+    ("rectangle", Action::DrawTool(Tool::Rectangle)),
+    ("filled_rectangle", Action::DrawTool(Tool::FilledRectangle)),
+    ("ellipse", Action::DrawTool(Tool::Ellipse)),
+    ("filled_ellipse", Action::DrawTool(Tool::FilledEllipse)),
+    ("flood_fill", Action::DrawTool(Tool::FloodFill)),
+    ("mirror_mode", Action::MirrorMode),
+    ("mirror_center", Action::MirrorCenter),
+    ("stamp", Action::Stamp),
+    ("cancel", Action::Cancel),
+    ("rotate_left", Action::RotateLeft),
+    ("rotate_right", Action::RotateRight),
+    ("flip_horizontal", Action::FlipHorizontal),
+    ("flip_vertical", Action::FlipVertical),
+    ("soup", Action::Soup),
+    ("census", Action::Census),
+    ("auto_stop", Action::AutoStop),
+    ("next_pattern", Action::NextPattern),
+    ("previous_pattern", Action::PreviousPattern),
+    ("snapshot", Action::Snapshot),
+    ("save_session", Action::SaveSession),
+    ("graph", Action::Graph),
+    ("save_statistics", Action::SaveStatistics),
+    ("record_gif", Action::RecordGif),
+    ("record_apng", Action::RecordApng),
+    ("apgcode", Action::Apgcode),
+    ("pan_left", Action::PanLeft),
+    ("pan_right", Action::PanRight),
+    ("pan_up", Action::PanUp),
+    ("pan_down", Action::PanDown),
+    ("zoom_in", Action::ZoomIn),
+    ("zoom_out", Action::ZoomOut),
+    ("cursor_left", Action::CursorLeft),
+    ("cursor_right", Action::CursorRight),
+    ("cursor_up", Action::CursorUp),
+    ("cursor_down", Action::CursorDown),
+    ("toggle_cell", Action::ToggleCell),
+];
+
+/// Same syntax as the config file.
+const DEFAULT_BINDINGS: &str = "
+play = Shift+Space
+pause = Shift+Enter
+toggle_play = Enter
+step = /
+step_10 = Shift+/
+slower = ,
+faster = .
+reset = Q
+undo = Ctrl+Z
+redo = Ctrl+Shift+Z, Ctrl+Y
+copy = Ctrl+C
+cut = Ctrl+X
+paste = Ctrl+V
+fill_random = Ctrl+R
+invert = Ctrl+I
+clear_selected = Delete, Backspace
+scrub_back = [
+scrub_back_10 = Shift+[
+scrub_forward = ]
+scrub_forward_10 = Shift+]
+select_tool = M
+edit = Tab
+pencil = 1
+line = 2
+rectangle = 3
+filled_rectangle = 4
+ellipse = 5
+filled_ellipse = 6
+flood_fill = 7
+mirror_mode = U
+mirror_center = Y
+stamp = T
+cancel = Escape
+rotate_left = E
+rotate_right = R
+flip_horizontal = F
+flip_vertical = V
+soup = O
+census = I
+auto_stop = X
+next_pattern = N
+previous_pattern = B
+snapshot = P
+save_session = K
+graph = J
+save_statistics = L
+record_gif = G
+record_apng = H
+apgcode = C
+pan_left = Left, A
+pan_right = Right, D
+pan_up = Up, W
+pan_down = Down, S
+zoom_in = =, Numpad+
+zoom_out = -, Numpad-
+cursor_left = Shift+Left
+cursor_right = Shift+Right
+cursor_up = Shift+Up
+cursor_down = Shift+Down
+toggle_cell = Space
+";
+
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("A", KeyCode::KeyA),
+    ("B", KeyCode::KeyB),
+    ("C", KeyCode::KeyC),
+    ("D", KeyCode::KeyD),
+    ("E", KeyCode::KeyE),
+    ("F", KeyCode::KeyF),
+    ("G", KeyCode::KeyG),
+    ("H", KeyCode::KeyH),
+    ("I", KeyCode::KeyI),
+    ("J", KeyCode::KeyJ),
+    ("K", KeyCode::KeyK),
+    ("L", KeyCode::KeyL),
+    ("M", KeyCode::KeyM),
+    ("N", KeyCode::KeyN),
+    ("O", KeyCode::KeyO),
+    ("P", KeyCode::KeyP),
+    ("Q", KeyCode::KeyQ),
+    ("R", KeyCode::KeyR),
+    ("S", KeyCode::KeyS),
+    ("T", KeyCode::KeyT),
+    ("U", KeyCode::KeyU),
+    ("V", KeyCode::KeyV),
+    ("W", KeyCode::KeyW),
+    ("X", KeyCode::KeyX),
+    ("Y", KeyCode::KeyY),
+    ("Z", KeyCode::KeyZ),
+    ("0", KeyCode::Digit0),
+    ("1", KeyCode::Digit1),
+    ("2", KeyCode::Digit2),
+    ("3", KeyCode::Digit3),
+    ("4", KeyCode::Digit4),
+    ("5", KeyCode::Digit5),
+    ("6", KeyCode::Digit6),
+    ("7", KeyCode::Digit7),
+    ("8", KeyCode::Digit8),
+    ("9", KeyCode::Digit9),
+    ("F1", KeyCode::F1),
+    ("F2", KeyCode::F2),
+    ("F3", KeyCode::F3),
+    ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5),
+    ("F6", KeyCode::F6),
+    ("F7", KeyCode::F7),
+    ("F8", KeyCode::F8),
+    ("F9", KeyCode::F9),
+    ("F10", KeyCode::F10),
+    ("F11", KeyCode::F11),
+    ("F12", KeyCode::F12),
+    ("Left", KeyCode::ArrowLeft),
+    ("Right", KeyCode::ArrowRight),
+    ("Up", KeyCode::ArrowUp),
+    ("Down", KeyCode::ArrowDown),
+    ("Space", KeyCode::Space),
+    ("Enter", KeyCode::Enter),
+    ("Tab", KeyCode::Tab),
+    ("Escape", KeyCode::Escape),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("-", KeyCode::Minus),
+    ("=", KeyCode::Equal),
+    ("[", KeyCode::BracketLeft),
+    ("]", KeyCode::BracketRight),
+    (";", KeyCode::Semicolon),
+    ("'", KeyCode::Quote),
+    ("`", KeyCode::Backquote),
+    ("\\", KeyCode::Backslash),
+    (",", KeyCode::Comma),
+    (".", KeyCode::Period),
+    ("/", KeyCode::Slash),
+    ("Numpad+", KeyCode::NumpadAdd),
+    ("Numpad-", KeyCode::NumpadSubtract),
+];
+
+impl KeyBinding {
+    /// Only the modifiers bindings can ask for, with the command key folded into ctrl.
+    fn normalize(modifiers: ModifiersState) -> ModifiersState {
+        let mut normalized = modifiers & (ModifiersState::SHIFT | ModifiersState::ALT);
+        if modifiers.control_key() || modifiers.super_key() {
+            normalized |= ModifiersState::CONTROL;
+        }
+        normalized
+    }
+}
+
+impl std::str::FromStr for KeyBinding {
+    type Err = Error;
+
+    /// `Ctrl+Shift+Z`, the key last
+    fn from_str(s: &str) -> Result<Self> {
+        // "Ctrl+=" 처럼 키 이름에 + 가 붙는 경우가 있어서 마지막 + 뒤 한 글자 이상을 키로 본다
+        let s = s.trim();
+        let (modifiers, key) = match s
+            .get(..s.len().saturating_sub(1))
+            .and_then(|head| head.rfind('+'))
+        {
+            Some(split) => (&s[..split], &s[split + 1..]),
+            None => ("", s),
+        };
+        let mut state = ModifiersState::empty();
+        for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+            state |= match modifier.trim().to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" | "command" => ModifiersState::CONTROL,
+                "shift" => ModifiersState::SHIFT,
+                "alt" => ModifiersState::ALT,
+                _ => bail!("unknown modifier {} in {}", modifier, s),
+            };
+        }
+        let Some(&(_, key)) = KEY_NAMES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key.trim()))
+        else {
+            bail!("unknown key {}", key);
+        };
+        Ok(KeyBinding {
+            key,
+            modifiers: state,
+        })
+    }
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let mut key_map = KeyMap {
+            bindings: HashMap::new(),
+        };
+        key_map.load(DEFAULT_BINDINGS).unwrap();
+        key_map
+    }
+}
+
+impl KeyMap {
+    /// Defaults with the actions listed in `text` rebound.
+    pub fn parse(text: &str) -> Result<KeyMap> {
+        let mut key_map = KeyMap::default();
+        key_map.load(text)?;
+        Ok(key_map)
+    }
+
+    /// `#` starts a comment, and an empty right side unbinds the action.
+    fn load(&mut self, text: &str) -> Result<()> {
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let Some((name, keys)) = line.split_once('=') else {
+                bail!("line {}: expected action = key", number + 1);
+            };
+            let Some(&(_, action)) = ACTION_NAMES.iter().find(|(n, _)| *n == name.trim()) else {
+                bail!("line {}: unknown action {}", number + 1, name.trim());
+            };
+            let bindings = keys
+                .split(',')
+                .filter(|key| !key.trim().is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<KeyBinding>>>()
+                .with_context(|| format!("line {}", number + 1))?;
+            self.bindings.insert(action, bindings);
+        }
+        Ok(())
+    }
+
+    /// Action bound to exactly this key and these modifiers.
+    pub fn action(&self, key: KeyCode, modifiers: ModifiersState) -> Option<Action> {
+        let pressed = KeyBinding {
+            key,
+            modifiers: KeyBinding::normalize(modifiers),
+        };
+        ACTION_NAMES
+            .iter()
+            .map(|(_, action)| *action)
+            .find(|action| {
+                self.bindings
+                    .get(action)
+                    .map_or(false, |bindings| bindings.contains(&pressed))
+            })
+    }
+
+    /// Whether a key bound to `action` is down with its modifiers, for actions that last while held.
+    pub fn is_held(&self, action: Action, input_handler: &InputHandler) -> bool {
+        self.any_binding(action, input_handler, |key| input_handler.is_key_held(key))
+    }
+
+    /// Whether a key bound to `action` went down since the systems last ran.
+    pub fn is_pressed(&self, action: Action, input_handler: &InputHandler) -> bool {
+        self.any_binding(action, input_handler, |key| {
+            input_handler.is_key_pressed(key)
+        })
+    }
+
+    fn any_binding(
+        &self,
+        action: Action,
+        input_handler: &InputHandler,
+        is_down: impl Fn(KeyCode) -> bool,
+    ) -> bool {
+        let modifiers = KeyBinding::normalize(input_handler.modifiers);
+        self.bindings.get(&action).map_or(false, |bindings| {
+            bindings
+                .iter()
+                .any(|binding| binding.modifiers == modifiers && is_down(binding.key))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn defaults_bind_each_key_once() {
+        let key_map = KeyMap::default();
+        let mut seen = HashSet::new();
+        for bindings in key_map.bindings.values() {
+            for binding in bindings {
+                assert!(seen.insert(*binding), "{:?} is bound twice", binding);
+            }
+        }
+        assert_eq!(
+            key_map.action(KeyCode::Space, ModifiersState::SHIFT),
+            Some(Action::Play)
+        );
+        assert_eq!(
+            key_map.action(KeyCode::Enter, ModifiersState::empty()),
+            Some(Action::TogglePlay)
+        );
+    }
+
+    #[test]
+    fn command_counts_as_ctrl() {
+        let key_map = KeyMap::default();
+        assert_eq!(
+            key_map.action(KeyCode::KeyZ, ModifiersState::SUPER),
+            Some(Action::Undo)
+        );
+        assert_eq!(
+            key_map.action(
+                KeyCode::KeyZ,
+                ModifiersState::CONTROL | ModifiersState::SHIFT
+            ),
+            Some(Action::Redo)
+        );
+    }
+
+    #[test]
+    fn files_rebind_and_unbind_over_the_defaults() {
+        let key_map = KeyMap::parse("# my keys\nundo = U, Ctrl+=  # both\nredo =\n").unwrap();
+        assert_eq!(
+            key_map.action(KeyCode::KeyU, ModifiersState::empty()),
+            Some(Action::Undo)
+        );
+        assert_eq!(
+            key_map.action(KeyCode::Equal, ModifiersState::CONTROL),
+            Some(Action::Undo)
+        );
+        assert_eq!(key_map.action(KeyCode::KeyZ, ModifiersState::CONTROL), None);
+        assert_eq!(key_map.action(KeyCode::KeyY, ModifiersState::CONTROL), None);
+        assert_eq!(
+            key_map.action(KeyCode::Escape, ModifiersState::empty()),
+            Some(Action::Cancel)
+        );
+    }
+
+    #[test]
+    fn reports_the_broken_line() {
+        for (text, message) in [
+            ("undo Ctrl+Z", "line 1: expected action = key"),
+            ("\nfly = F", "line 2: unknown action fly"),
+            ("undo = Hyper+Z", "unknown modifier Hyper in Hyper+Z"),
+            ("undo = Ctrl+Nope", "unknown key Nope"),
+        ] {
+            let error = KeyMap::parse(text).err().unwrap();
+            assert!(
+                format!("{:#}", error).contains(message),
+                "{:#} should mention {}",
+                error,
+                message
+            );
+        }
+    }
+}
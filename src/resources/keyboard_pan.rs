@@ -0,0 +1,5 @@
+/// How long the pan keys have been held, for speeding up the longer they are.
+#[derive(Default)]
+pub struct KeyboardPan {
+    pub held_for: f32,
+}
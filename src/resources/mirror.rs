@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+
+use crate::configs;
+use crate::pattern::Orientation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MirrorMode {
+    #[default]
+    Off,
+    /// left and right halves mirror each other across the vertical line through the center
+    Horizontal,
+    /// top and bottom halves mirror each other across the horizontal line through the center
+    Vertical,
+    /// mirrored across the diagonal going up to the right
+    Diagonal,
+    /// half turns about the center
+    TwoFold,
+    /// quarter turns about the center
+    FourFold,
+    /// quarter turns and mirrors, the full symmetry of the square
+    EightFold,
+}
+
+/// Symmetry every drawn cell is repeated with, about `center`.
+#[derive(Default)]
+pub struct Mirror {
+    pub mode: MirrorMode,
+    /// world cell the axes go through
+    pub center: [i32; 2],
+}
+
+impl MirrorMode {
+    pub const ALL: [MirrorMode; 7] = [
+        MirrorMode::Off,
+        MirrorMode::Horizontal,
+        MirrorMode::Vertical,
+        MirrorMode::Diagonal,
+        MirrorMode::TwoFold,
+        MirrorMode::FourFold,
+        MirrorMode::EightFold,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MirrorMode::Off => "off",
+            MirrorMode::Horizontal => "horizontal",
+            MirrorMode::Vertical => "vertical",
+            MirrorMode::Diagonal => "diagonal",
+            MirrorMode::TwoFold => "2-fold",
+            MirrorMode::FourFold => "4-fold",
+            MirrorMode::EightFold => "8-fold",
+        }
+    }
+
+    fn orientations(&self) -> &'static [Orientation] {
+        use Orientation::*;
+        match self {
+            MirrorMode::Off => &[Identity],
+            MirrorMode::Horizontal => &[Identity, FlipX],
+            MirrorMode::Vertical => &[Identity, FlipY],
+            MirrorMode::Diagonal => &[Identity, Transpose],
+            MirrorMode::TwoFold => &[Identity, Rotate180],
+            MirrorMode::FourFold => &[Identity, Rotate90, Rotate180, Rotate270],
+            MirrorMode::EightFold => &Orientation::ALL,
+        }
+    }
+
+    /// Next mode, wrapping back to off.
+    pub fn next(&self) -> MirrorMode {
+        let index = MirrorMode::ALL
+            .iter()
+            .position(|mode| mode == self)
+            .unwrap();
+        MirrorMode::ALL[(index + 1) % MirrorMode::ALL.len()]
+    }
+}
+
+impl Mirror {
+    /// `cell` and its mirror images, each once even when `cell` lies on an axis.
+    pub fn images(&self, cell: [i32; 2]) -> Vec<[i32; 2]> {
+        let [cx, cy] = self.center;
+        let mut images = Vec::with_capacity(self.mode.orientations().len());
+        for orientation in self.mode.orientations() {
+            let [x, y] = orientation.apply([cell[0] - cx, cell[1] - cy]);
+            if !images.contains(&[x + cx, y + cy]) {
+                images.push([x + cx, y + cy]);
+            }
+        }
+        images
+    }
+
+    /// Images of all `cells`, each once, so toggling edits don't flip a shared image back.
+    pub fn mirrored(&self, cells: &[[i32; 2]]) -> Vec<[i32; 2]> {
+        let mut seen = HashSet::new();
+        cells
+            .iter()
+            .flat_map(|&cell| self.images(cell))
+            .filter(|&cell| seen.insert(cell))
+            .collect()
+    }
+
+    /// Axes to draw over the grid as `(from, to)` in world coordinates, clipped to the grid.
+    /// Rotations without mirror axes get a small cross on the center.
+    pub fn guides(&self) -> Vec<([f32; 2], [f32; 2])> {
+        let center = [self.center[0] as f32, self.center[1] as f32];
+        let directions: &[[f32; 2]] = match self.mode {
+            MirrorMode::Off => return vec![],
+            MirrorMode::Horizontal => &[[0.0, 1.0]],
+            MirrorMode::Vertical => &[[1.0, 0.0]],
+            MirrorMode::Diagonal => &[[1.0, 1.0]],
+            MirrorMode::TwoFold | MirrorMode::FourFold => {
+                return [[1.0, 0.0], [0.0, 1.0]]
+                    .iter()
+                    .map(|&[dx, dy]| {
+                        (
+                            [center[0] - dx, center[1] - dy],
+                            [center[0] + dx, center[1] + dy],
+                        )
+                    })
+                    .collect();
+            }
+            MirrorMode::EightFold => &[[1.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, -1.0]],
+        };
+        directions
+            .iter()
+            .filter_map(|&direction| clip_to_grid(center, direction))
+            .collect()
+    }
+}
+
+/// The part of the line through `point` along `direction` that crosses the grid.
+fn clip_to_grid(point: [f32; 2], direction: [f32; 2]) -> Option<([f32; 2], [f32; 2])> {
+    let half = [
+        configs::GRID_SIZE_HALF[0] as f32 + 0.5,
+        configs::GRID_SIZE_HALF[1] as f32 + 0.5,
+    ];
+    let mut range = [f32::NEG_INFINITY, f32::INFINITY];
+    for axis in 0..2 {
+        if direction[axis] == 0.0 {
+            if point[axis].abs() > half[axis] {
+                return None;
+            }
+            continue;
+        }
+        let t0 = (-half[axis] - point[axis]) / direction[axis];
+        let t1 = (half[axis] - point[axis]) / direction[axis];
+        range = [range[0].max(t0.min(t1)), range[1].min(t0.max(t1))];
+    }
+    if range[0] >= range[1] {
+        return None;
+    }
+    let at = |t: f32| [point[0] + direction[0] * t, point[1] + direction[1] * t];
+    Some((at(range[0]), at(range[1])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mirror(mode: MirrorMode) -> Mirror {
+        Mirror {
+            mode,
+            center: [3, -1],
+        }
+    }
+
+    fn sorted(mut cells: Vec<[i32; 2]>) -> Vec<[i32; 2]> {
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn repeats_a_cell_once_per_orientation() {
+        let expected: [(MirrorMode, Vec<[i32; 2]>); 6] = [
+            (MirrorMode::Off, vec![[5, 0]]),
+            (MirrorMode::Horizontal, vec![[5, 0], [1, 0]]),
+            (MirrorMode::Vertical, vec![[5, 0], [5, -2]]),
+            (MirrorMode::Diagonal, vec![[5, 0], [4, 1]]),
+            (MirrorMode::TwoFold, vec![[5, 0], [1, -2]]),
+            (MirrorMode::FourFold, vec![[5, 0], [2, 1], [1, -2], [4, -3]]),
+        ];
+        for (mode, cells) in expected {
+            assert_eq!(
+                sorted(mirror(mode).images([5, 0])),
+                sorted(cells),
+                "{:?}",
+                mode
+            );
+        }
+
+        let images = mirror(MirrorMode::EightFold).images([5, 0]);
+        assert_eq!(images.iter().collect::<HashSet<_>>().len(), 8);
+    }
+
+    #[test]
+    fn cells_on_an_axis_are_not_repeated() {
+        for mode in MirrorMode::ALL {
+            assert_eq!(mirror(mode).images([3, -1]), vec![[3, -1]], "{:?}", mode);
+        }
+        assert_eq!(mirror(MirrorMode::Horizontal).images([3, 5]), vec![[3, 5]]);
+        assert_eq!(mirror(MirrorMode::Vertical).images([9, -1]), vec![[9, -1]]);
+        assert_eq!(mirror(MirrorMode::Diagonal).images([5, 1]), vec![[5, 1]]);
+        assert_eq!(
+            sorted(mirror(MirrorMode::EightFold).images([3, 5])),
+            vec![[-3, -1], [3, -7], [3, 5], [9, -1]]
+        );
+
+        // 축을 가로지르는 줄은 겹치는 칸을 한 번만 남긴다
+        let cells = mirror(MirrorMode::Horizontal).mirrored(&[[2, 0], [3, 0], [4, 0]]);
+        assert_eq!(sorted(cells), vec![[2, 0], [3, 0], [4, 0]]);
+    }
+
+    #[test]
+    fn guides_follow_the_axes_across_the_grid() {
+        let half = [
+            configs::GRID_SIZE_HALF[0] as f32 + 0.5,
+            configs::GRID_SIZE_HALF[1] as f32 + 0.5,
+        ];
+        assert!(mirror(MirrorMode::Off).guides().is_empty());
+        assert_eq!(
+            mirror(MirrorMode::Horizontal).guides(),
+            vec![([3.0, -half[1]], [3.0, half[1]])]
+        );
+        assert_eq!(
+            mirror(MirrorMode::Vertical).guides(),
+            vec![([-half[0], -1.0], [half[0], -1.0])]
+        );
+        let [(from, to)] = mirror(MirrorMode::Diagonal).guides()[..] else {
+            panic!("one diagonal expected");
+        };
+        assert_eq!(to[0] - from[0], to[1] - from[1]);
+        assert_eq!([from[1], to[1]], [-half[1], half[1]]);
+
+        assert_eq!(mirror(MirrorMode::TwoFold).guides().len(), 2);
+        assert_eq!(mirror(MirrorMode::FourFold).guides().len(), 2);
+        let guides = mirror(MirrorMode::EightFold).guides();
+        assert_eq!(guides.len(), 4);
+        for (from, to) in guides {
+            for [x, y] in [from, to] {
+                assert!(x.abs() <= half[0] && y.abs() <= half[1]);
+            }
+        }
+
+        let outside = Mirror {
+            mode: MirrorMode::Horizontal,
+            center: [100, 0],
+        };
+        assert!(outside.guides().is_empty());
+    }
+}
@@ -1,7 +1,39 @@
+pub use auto_stop::{AutoStop, StopReason};
 pub use camera::Camera;
+pub use cell_cursor::CellCursor;
+pub use context_menu::{ContextAction, ContextMenu};
+pub use cycle_detector::{Cycle, CycleDetector};
+pub use draw_tool::{DrawTool, Drawn, Tool};
+pub use frame_time::FrameTime;
+pub use history::History;
 pub use input_handler::{InputHandler, MouseButtons};
-pub use stage_tick::StageTick;
+pub use key_map::{Action, KeyMap};
+pub use keyboard_pan::KeyboardPan;
+pub use mirror::{Mirror, MirrorMode};
+pub use selection::Selection;
+pub use simulation_clock::{ClockState, SimulationClock, Speed};
+pub use statistics::{Metric, Statistics};
+pub use stepped::Stepped;
+pub use stroke::Stroke;
+pub use timeline::Timeline;
+pub use touch_gesture::{Gesture, TouchGesture};
 
+mod auto_stop;
 mod camera;
+mod cell_cursor;
+mod context_menu;
+mod cycle_detector;
+mod draw_tool;
+mod frame_time;
+mod history;
 mod input_handler;
-mod stage_tick;
+mod key_map;
+mod keyboard_pan;
+mod mirror;
+mod selection;
+mod simulation_clock;
+mod statistics;
+mod stepped;
+mod stroke;
+mod timeline;
+mod touch_gesture;
@@ -0,0 +1,26 @@
+/// Rectangle of cells picked with the select tool.
+#[derive(Default)]
+pub struct Selection {
+    /// left drags pick a rectangle instead of painting while this is on
+    pub enabled: bool,
+    /// cell the current drag started from
+    pub anchor: Option<[i32; 2]>,
+    /// `[x_min, y_min, x_max, y_max]` in world cells, y up
+    pub rect: Option<[i32; 4]>,
+}
+
+impl Selection {
+    pub fn cells(&self) -> Vec<[i32; 2]> {
+        let Some([x0, y0, x1, y1]) = self.rect else {
+            return vec![];
+        };
+        (x0..=x1)
+            .flat_map(|x| (y0..=y1).map(move |y| [x, y]))
+            .collect()
+    }
+
+    /// The same rectangle in pattern coordinates, y down.
+    pub fn pattern_region(&self) -> Option<[i32; 4]> {
+        self.rect.map(|[x0, y0, x1, y1]| [x0, -y1, x1, -y0])
+    }
+}
@@ -0,0 +1,272 @@
+use crate::configs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockState {
+    Paused,
+    Running,
+    /// runs this many more generations, then pauses
+    Stepping(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Speed {
+    /// fixed time step, independent of how fast frames are drawn
+    PerSecond(f32),
+    /// this many generations on every frame
+    PerFrame(u32),
+}
+
+/// Decides how many generations are due each frame and counts them.
+pub struct SimulationClock {
+    pub state: ClockState,
+    pub speed: Speed,
+    /// generations stepped since the grid was seeded
+    pub generation: u64,
+    /// time not yet spent on a whole generation
+    accumulator: f64,
+    /// generations left to step in the current frame
+    due: u32,
+}
+
+impl Default for SimulationClock {
+    fn default() -> Self {
+        SimulationClock {
+            state: ClockState::Paused,
+            speed: Speed::PerSecond(configs::GENERATIONS_PER_SECOND),
+            generation: 0,
+            accumulator: 0.0,
+            due: 0,
+        }
+    }
+}
+
+impl SimulationClock {
+    pub fn run(&mut self) {
+        self.state = ClockState::Running;
+        self.accumulator = 0.0;
+    }
+
+    pub fn pause(&mut self) {
+        self.state = ClockState::Paused;
+        self.accumulator = 0.0;
+        self.due = 0;
+    }
+
+    pub fn step(&mut self, generations: u64) {
+        self.state = match generations {
+            0 => ClockState::Paused,
+            n => ClockState::Stepping(n),
+        };
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state == ClockState::Running
+    }
+
+    /// Starts a frame `dt` seconds after the last one.
+    pub fn advance(&mut self, dt: f32) {
+        self.due = match (self.state, self.speed) {
+            (ClockState::Paused, _) => 0,
+            (ClockState::Stepping(n), _) => n.min(configs::MAX_STEPS_PER_FRAME as u64) as u32,
+            (ClockState::Running, Speed::PerFrame(n)) => n.min(configs::MAX_STEPS_PER_FRAME),
+            (ClockState::Running, Speed::PerSecond(rate)) => {
+                self.accumulator += dt as f64;
+                let period = 1.0 / rate.max(f32::EPSILON) as f64;
+                let due = (self.accumulator / period).floor();
+                if due > configs::MAX_STEPS_PER_FRAME as f64 {
+                    // 따라잡지 못할 만큼 밀렸으면 밀린 시간은 버린다
+                    self.accumulator = 0.0;
+                    configs::MAX_STEPS_PER_FRAME
+                } else {
+                    self.accumulator -= due * period;
+                    due as u32
+                }
+            }
+        };
+    }
+
+    pub fn has_due(&self) -> bool {
+        self.due > 0
+    }
+
+    /// Counts one due generation and returns its number, or `None` when this frame is done.
+    pub fn take_step(&mut self) -> Option<u64> {
+        if self.due == 0 {
+            return None;
+        }
+        self.due -= 1;
+        self.generation += 1;
+        if let ClockState::Stepping(n) = self.state {
+            self.state = match n - 1 {
+                0 => ClockState::Paused,
+                n => ClockState::Stepping(n),
+            };
+        }
+        Some(self.generation)
+    }
+
+    /// Twice or half as fast.
+    pub fn scale_speed(&mut self, faster: bool) {
+        self.speed = match self.speed {
+            Speed::PerSecond(rate) => Speed::PerSecond(if faster {
+                (rate * 2.0).min(configs::MAX_GENERATIONS_PER_SECOND)
+            } else {
+                (rate / 2.0).max(configs::MIN_GENERATIONS_PER_SECOND)
+            }),
+            Speed::PerFrame(n) => Speed::PerFrame(if faster {
+                n.saturating_mul(2).min(configs::MAX_STEPS_PER_FRAME)
+            } else {
+                (n / 2).max(1)
+            }),
+        };
+    }
+}
+
+impl Speed {
+    /// Keeps the speed within what the clock supports.
+    pub fn clamped(self) -> Speed {
+        match self {
+            // NaN 은 max 에서 가장 느린 속도가 된다
+            Speed::PerSecond(rate) => Speed::PerSecond(
+                rate.max(configs::MIN_GENERATIONS_PER_SECOND)
+                    .min(configs::MAX_GENERATIONS_PER_SECOND),
+            ),
+            Speed::PerFrame(n) => Speed::PerFrame(n.clamp(1, configs::MAX_STEPS_PER_FRAME)),
+        }
+    }
+}
+
+impl std::fmt::Display for Speed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Speed::PerSecond(rate) => write!(f, "{} gen/s", rate),
+            Speed::PerFrame(n) => write!(f, "{} gen/frame", n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(clock: &mut SimulationClock) -> Vec<u64> {
+        std::iter::from_fn(|| clock.take_step()).collect()
+    }
+
+    #[test]
+    fn runs_at_a_fixed_rate_whatever_the_frame_time() {
+        let mut clock = SimulationClock {
+            speed: Speed::PerSecond(4.0),
+            ..Default::default()
+        };
+        clock.advance(1.0);
+        assert!(!clock.has_due());
+
+        clock.run();
+        clock.advance(0.5);
+        assert_eq!(steps(&mut clock), [1, 2]);
+        // 남은 시간은 다음 프레임으로 넘어간다
+        clock.advance(0.125);
+        assert_eq!(steps(&mut clock), []);
+        clock.advance(0.125);
+        assert_eq!(steps(&mut clock), [3]);
+    }
+
+    #[test]
+    fn drops_time_it_cannot_catch_up_on() {
+        let mut clock = SimulationClock {
+            speed: Speed::PerSecond(configs::MAX_GENERATIONS_PER_SECOND),
+            ..Default::default()
+        };
+        clock.run();
+        clock.advance(10.0);
+        assert_eq!(
+            steps(&mut clock).len(),
+            configs::MAX_STEPS_PER_FRAME as usize
+        );
+        clock.advance(0.0);
+        assert!(!clock.has_due());
+    }
+
+    #[test]
+    fn steps_a_fixed_count_then_pauses() {
+        let mut clock = SimulationClock::default();
+        clock.step(3);
+        assert_eq!(clock.state, ClockState::Stepping(3));
+        clock.advance(0.0);
+        assert_eq!(clock.take_step(), Some(1));
+        assert_eq!(clock.state, ClockState::Stepping(2));
+        assert_eq!(steps(&mut clock), [2, 3]);
+        assert_eq!(clock.state, ClockState::Paused);
+        clock.advance(1.0);
+        assert_eq!(clock.take_step(), None);
+
+        clock.step(0);
+        assert_eq!(clock.state, ClockState::Paused);
+    }
+
+    #[test]
+    fn spreads_long_steps_over_frames() {
+        let max = configs::MAX_STEPS_PER_FRAME as u64;
+        let mut clock = SimulationClock::default();
+        clock.step(max + 5);
+        clock.advance(0.0);
+        assert_eq!(steps(&mut clock).len() as u64, max);
+        assert_eq!(clock.state, ClockState::Stepping(5));
+        clock.advance(0.0);
+        assert_eq!(steps(&mut clock).len(), 5);
+        assert_eq!(clock.generation, max + 5);
+        assert_eq!(clock.state, ClockState::Paused);
+    }
+
+    #[test]
+    fn runs_a_fixed_count_every_frame() {
+        let mut clock = SimulationClock {
+            speed: Speed::PerFrame(5),
+            ..Default::default()
+        };
+        clock.run();
+        clock.advance(0.0);
+        assert_eq!(steps(&mut clock), [1, 2, 3, 4, 5]);
+        clock.pause();
+        clock.advance(1.0);
+        assert!(!clock.has_due());
+    }
+
+    #[test]
+    fn clamps_speeds_to_what_the_clock_supports() {
+        assert_eq!(
+            Speed::PerSecond(f32::NAN).clamped(),
+            Speed::PerSecond(configs::MIN_GENERATIONS_PER_SECOND)
+        );
+        assert_eq!(
+            Speed::PerSecond(f32::INFINITY).clamped(),
+            Speed::PerSecond(configs::MAX_GENERATIONS_PER_SECOND)
+        );
+        assert_eq!(Speed::PerSecond(3.0).clamped(), Speed::PerSecond(3.0));
+        assert_eq!(Speed::PerFrame(0).clamped(), Speed::PerFrame(1));
+        assert_eq!(
+            Speed::PerFrame(u32::MAX).clamped(),
+            Speed::PerFrame(configs::MAX_STEPS_PER_FRAME)
+        );
+    }
+
+    #[test]
+    fn scales_within_the_limits() {
+        let mut clock = SimulationClock {
+            speed: Speed::PerFrame(u32::MAX),
+            ..Default::default()
+        };
+        clock.scale_speed(true);
+        assert_eq!(clock.speed, Speed::PerFrame(configs::MAX_STEPS_PER_FRAME));
+        clock.speed = Speed::PerFrame(1);
+        clock.scale_speed(false);
+        assert_eq!(clock.speed, Speed::PerFrame(1));
+        clock.speed = Speed::PerSecond(configs::MIN_GENERATIONS_PER_SECOND);
+        clock.scale_speed(false);
+        assert_eq!(
+            clock.speed,
+            Speed::PerSecond(configs::MIN_GENERATIONS_PER_SECOND)
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::pattern::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationStats {
+    pub generation: u64,
+    pub population: usize,
+    /// cells that were dead in the previously recorded generation
+    pub births: usize,
+    pub deaths: usize,
+    /// `[x_min, y_min, x_max, y_max]` in pattern coordinates
+    pub bounding_box: Option<[i32; 4]>,
+    /// live fraction of the whole grid
+    pub density: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Population,
+    Births,
+    Deaths,
+    Density,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+}
+
+/// Time series of per-generation numbers, keeping at most `capacity` of the latest generations.
+pub struct Statistics {
+    samples: VecDeque<GenerationStats>,
+    capacity: usize,
+    previous: Option<Grid>,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 4] = [
+        Metric::Population,
+        Metric::Births,
+        Metric::Deaths,
+        Metric::Density,
+    ];
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Metric::Population => write!(f, "population"),
+            Metric::Births => write!(f, "births"),
+            Metric::Deaths => write!(f, "deaths"),
+            Metric::Density => write!(f, "density"),
+        }
+    }
+}
+
+impl GenerationStats {
+    fn value(&self, metric: Metric) -> f64 {
+        match metric {
+            Metric::Population => self.population as f64,
+            Metric::Births => self.births as f64,
+            Metric::Deaths => self.deaths as f64,
+            Metric::Density => self.density as f64,
+        }
+    }
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Statistics::new(crate::configs::STATISTICS_CAPACITY)
+    }
+}
+
+impl Statistics {
+    pub fn new(capacity: usize) -> Self {
+        Statistics {
+            samples: VecDeque::new(),
+            capacity,
+            previous: None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.previous = None;
+    }
+
+    /// Counts the births and deaths of the next recorded generation against `grid`,
+    /// for when the cells change without a step, e.g. on undo or a jump along the timeline.
+    pub fn rebase(&mut self, grid: &Grid) {
+        self.previous = Some(grid.clone());
+    }
+
+    /// Same as `rebase` after cells were flipped by hand, for the cells given by `Cell::index`.
+    pub fn record_edit(&mut self, flips: &[u32]) {
+        if let Some(previous) = &mut self.previous {
+            for &index in flips {
+                let alive = previous.cells()[index as usize];
+                previous.set(index as usize, !alive);
+            }
+        }
+    }
+
+    pub fn samples(&self) -> impl DoubleEndedIterator<Item = &GenerationStats> + ExactSizeIterator {
+        self.samples.iter()
+    }
+
+    /// Samples from `generation` on are replaced, e.g. after going back on the timeline.
+    pub fn record(&mut self, generation: u64, grid: &Grid) {
+        while self
+            .samples
+            .back()
+            .map_or(false, |sample| sample.generation >= generation)
+        {
+            self.samples.pop_back();
+        }
+        let cells = grid.cells();
+        let (births, deaths) = match &self.previous {
+            Some(previous) if previous.cells().len() == cells.len() => previous
+                .cells()
+                .iter()
+                .zip(cells)
+                .fold((0, 0), |(births, deaths), (&before, &after)| {
+                    (
+                        births + (!before && after) as usize,
+                        deaths + (before && !after) as usize,
+                    )
+                }),
+            _ => (0, 0),
+        };
+        let population = cells.iter().filter(|alive| **alive).count();
+
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(GenerationStats {
+            generation,
+            population,
+            births,
+            deaths,
+            bounding_box: grid.to_pattern().bounding_box(),
+            density: population as f32 / cells.len().max(1) as f32,
+        });
+        self.previous = Some(grid.clone());
+    }
+
+    /// `None` when no recorded generation falls inside `generations`.
+    pub fn summary(&self, generations: RangeInclusive<u64>, metric: Metric) -> Option<Summary> {
+        let values = self
+            .samples
+            .iter()
+            .filter(|sample| generations.contains(&sample.generation))
+            .map(|sample| sample.value(metric))
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return None;
+        }
+        Some(Summary {
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+        })
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            "generation,population,births,deaths,x_min,y_min,x_max,y_max,density\n".to_string();
+        for sample in &self.samples {
+            // 살아있는 칸이 없으면 bounding box 칸은 비워 둔다
+            let bbox = match sample.bounding_box {
+                Some([x0, y0, x1, y1]) => format!("{},{},{},{}", x0, y0, x1, y1),
+                None => ",,,".to_string(),
+            };
+            csv += &format!(
+                "{},{},{},{},{},{}\n",
+                sample.generation,
+                sample.population,
+                sample.births,
+                sample.deaths,
+                bbox,
+                sample.density
+            );
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(alive: &[usize]) -> Grid {
+        let mut grid = Grid::new(3, 3);
+        for &index in alive {
+            grid.set(index, true);
+        }
+        grid
+    }
+
+    #[test]
+    fn counts_births_and_deaths_against_the_last_generation() {
+        let mut statistics = Statistics::new(10);
+        let blinker = grid(&[3, 4, 5]);
+        statistics.record(0, &blinker);
+        statistics.record(1, &blinker.step());
+
+        let samples = statistics.samples().collect::<Vec<_>>();
+        assert_eq!((samples[0].births, samples[0].deaths), (0, 0));
+        assert_eq!(samples[1].population, 3);
+        assert_eq!((samples[1].births, samples[1].deaths), (2, 2));
+    }
+
+    #[test]
+    fn edits_are_not_counted_as_births_or_deaths() {
+        let mut statistics = Statistics::new(10);
+        statistics.record(0, &grid(&[3, 4, 5]));
+        // 손으로 가운데 칸을 지운 뒤 다음 세대로 넘어간다
+        statistics.record_edit(&[4]);
+        statistics.record(1, &grid(&[]));
+        let last = statistics.samples().last().unwrap();
+        assert_eq!((last.births, last.deaths), (0, 2));
+
+        statistics.rebase(&grid(&[0]));
+        statistics.record(2, &grid(&[0, 8]));
+        let last = statistics.samples().last().unwrap();
+        assert_eq!((last.births, last.deaths), (1, 0));
+    }
+
+    #[test]
+    fn summarizes_a_range_of_generations() {
+        let mut statistics = Statistics::new(10);
+        for (generation, alive) in [&[0][..], &[0, 1, 2], &[0, 1], &[]].iter().enumerate() {
+            statistics.record(generation as u64, &grid(alive));
+        }
+        let population = statistics.summary(1..=2, Metric::Population).unwrap();
+        assert_eq!(
+            population,
+            Summary {
+                min: 2.0,
+                max: 3.0,
+                mean: 2.5
+            }
+        );
+        let births = statistics.summary(0..=3, Metric::Births).unwrap();
+        assert_eq!((births.min, births.max, births.mean), (0.0, 2.0, 0.5));
+        assert_eq!(statistics.summary(5..=9, Metric::Deaths), None);
+    }
+
+    #[test]
+    fn keeps_the_latest_generations_and_replaces_rewritten_ones() {
+        let mut statistics = Statistics::new(2);
+        for generation in 0..4 {
+            statistics.record(generation, &grid(&[]));
+        }
+        statistics.record(3, &grid(&[4]));
+        let kept = statistics
+            .samples()
+            .map(|sample| (sample.generation, sample.population))
+            .collect::<Vec<_>>();
+        assert_eq!(kept, [(2, 0), (3, 1)]);
+    }
+
+    #[test]
+    fn writes_a_csv_row_per_generation() {
+        let mut statistics = Statistics::new(10);
+        statistics.record(0, &grid(&[3, 4, 5]));
+        statistics.record(1, &grid(&[]));
+        let csv = statistics.to_csv();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "generation,population,births,deaths,x_min,y_min,x_max,y_max,density",
+                "0,3,0,0,0,-1,0,1,0.33333334",
+                "1,0,0,3,,,,,0",
+            ]
+        );
+    }
+}
@@ -0,0 +1,8 @@
+use crate::pattern::Grid;
+
+/// Generations `UpdateCells` produced during the current frame, oldest first,
+/// for the systems that watch the simulation.
+#[derive(Default)]
+pub struct Stepped {
+    pub generations: Vec<(u64, Grid)>,
+}
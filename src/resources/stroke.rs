@@ -0,0 +1,6 @@
+/// Cell the mouse painted last while a button is held,
+/// so the next sample is joined to it with a line even when the mouse moved fast.
+#[derive(Default)]
+pub struct Stroke {
+    pub last_cell: Option<[i32; 2]>,
+}
@@ -0,0 +1,249 @@
+use std::collections::VecDeque;
+
+use crate::configs;
+use crate::pattern::Grid;
+
+/// A full grid followed by the cells flipped in each generation after it.
+struct Segment {
+    generation: u64,
+    keyframe: Grid,
+    deltas: Vec<Vec<u32>>,
+}
+
+impl Segment {
+    fn last_generation(&self) -> u64 {
+        self.generation + self.deltas.len() as u64
+    }
+
+    fn bytes(&self) -> usize {
+        self.keyframe.cells().len()
+            + self
+                .deltas
+                .iter()
+                .map(|delta| delta.len() * std::mem::size_of::<u32>())
+                .sum::<usize>()
+    }
+}
+
+/// Every generation computed so far, kept as periodic keyframes plus per-generation deltas
+/// so any of them can be rebuilt. The oldest segments are dropped past `capacity` bytes.
+pub struct Timeline {
+    segments: VecDeque<Segment>,
+    interval: usize,
+    capacity: usize,
+    /// the grid at the latest generation, to diff the next one against
+    latest: Grid,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Timeline::new(configs::KEYFRAME_INTERVAL, configs::TIMELINE_BYTES)
+    }
+}
+
+impl Timeline {
+    pub fn new(interval: usize, capacity: usize) -> Self {
+        Timeline {
+            segments: VecDeque::new(),
+            interval,
+            capacity,
+            latest: Grid::default(),
+        }
+    }
+
+    /// Starts over with `grid` as the only generation.
+    pub fn reset(&mut self, generation: u64, grid: &Grid) {
+        self.segments.clear();
+        self.push_keyframe(generation, grid);
+    }
+
+    /// `(first, last)` generation that can be rebuilt.
+    pub fn range(&self) -> Option<(u64, u64)> {
+        let first = self.segments.front()?.generation;
+        let last = self.segments.back()?.last_generation();
+        Some((first, last))
+    }
+
+    /// Records the grid reached at `generation`.
+    /// Anything recorded from `generation` on belongs to a history that was left, so it is dropped,
+    /// unless the same grid was already recorded there.
+    pub fn record(&mut self, generation: u64, grid: &Grid) {
+        // 되감은 뒤 같은 흐름을 다시 재생하는 중이면 앞으로의 기록을 살려 둔다
+        if self.grid_at(generation).as_ref() == Some(grid) {
+            return;
+        }
+        match self.range() {
+            Some((first, last)) if generation > first && generation <= last + 1 => {
+                self.truncate(generation - 1);
+            }
+            // 이어지지 않는 세대면 거기서부터 새로 기록한다
+            _ => {
+                self.reset(generation, grid);
+                return;
+            }
+        }
+
+        let last = self.segments.back_mut().unwrap();
+        if last.deltas.len() + 1 >= self.interval {
+            self.push_keyframe(generation, grid);
+        } else {
+            // 편집된 뒤의 스텝이라도 마지막 기록과의 차이라서 되살리면 그대로 나온다
+            let delta = self
+                .latest
+                .cells()
+                .iter()
+                .zip(grid.cells())
+                .enumerate()
+                .filter(|(_, (before, after))| before != after)
+                .map(|(index, _)| index as u32)
+                .collect();
+            last.deltas.push(delta);
+            self.latest = grid.clone();
+        }
+        self.trim();
+    }
+
+    /// Rewrites `generation` with `flips` applied, dropping what was recorded after it.
+    pub fn record_edit(&mut self, generation: u64, flips: &[u32]) {
+        let Some(mut grid) = self.grid_at(generation) else {
+            return;
+        };
+        for &index in flips {
+            let alive = grid.cells()[index as usize];
+            grid.set(index as usize, !alive);
+        }
+        self.record(generation, &grid);
+    }
+
+    /// Rebuilds the grid at `generation`, if it is still kept.
+    pub fn grid_at(&self, generation: u64) -> Option<Grid> {
+        let segment = self
+            .segments
+            .iter()
+            .rev()
+            .find(|segment| segment.generation <= generation)?;
+        if generation > segment.last_generation() {
+            return None;
+        }
+        let mut grid = segment.keyframe.clone();
+        for delta in &segment.deltas[..(generation - segment.generation) as usize] {
+            for &index in delta {
+                let alive = grid.cells()[index as usize];
+                grid.set(index as usize, !alive);
+            }
+        }
+        Some(grid)
+    }
+
+    /// Keeps generations up to and including `generation`.
+    fn truncate(&mut self, generation: u64) {
+        if self.segments.back().unwrap().last_generation() <= generation {
+            return;
+        }
+        while self.segments.len() > 1 && self.segments.back().unwrap().generation > generation {
+            self.segments.pop_back();
+        }
+        let last = self.segments.back_mut().unwrap();
+        last.deltas
+            .truncate(generation.saturating_sub(last.generation) as usize);
+        self.latest = self.grid_at(generation).unwrap();
+    }
+
+    fn push_keyframe(&mut self, generation: u64, grid: &Grid) {
+        self.segments.push_back(Segment {
+            generation,
+            keyframe: grid.clone(),
+            deltas: vec![],
+        });
+        self.latest = grid.clone();
+    }
+
+    fn trim(&mut self) {
+        let mut bytes = self.segments.iter().map(Segment::bytes).sum::<usize>();
+        while bytes > self.capacity && self.segments.len() > 1 {
+            bytes -= self.segments.pop_front().unwrap().bytes();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a 4x4 grid with only cell `n` alive, so every generation differs
+    fn grid(n: usize) -> Grid {
+        let mut grid = Grid::new(4, 4);
+        grid.set(n, true);
+        grid
+    }
+
+    fn played(generations: u64) -> Timeline {
+        let mut timeline = Timeline::new(3, usize::MAX);
+        timeline.reset(0, &grid(0));
+        for generation in 1..=generations {
+            timeline.record(generation, &grid(generation as usize));
+        }
+        timeline
+    }
+
+    #[test]
+    fn rebuilds_every_generation() {
+        let timeline = played(7);
+        assert_eq!(timeline.range(), Some((0, 7)));
+        for generation in 0..=7 {
+            assert_eq!(
+                timeline.grid_at(generation),
+                Some(grid(generation as usize))
+            );
+        }
+        assert_eq!(timeline.grid_at(8), None);
+    }
+
+    #[test]
+    fn drops_the_future_after_going_back() {
+        let mut timeline = played(7);
+        timeline.record(4, &grid(15));
+        assert_eq!(timeline.range(), Some((0, 4)));
+        assert_eq!(timeline.grid_at(3), Some(grid(3)));
+        assert_eq!(timeline.grid_at(4), Some(grid(15)));
+        assert_eq!(timeline.grid_at(5), None);
+
+        // 새 흐름도 이어서 기록된다
+        timeline.record(5, &grid(14));
+        assert_eq!(timeline.grid_at(5), Some(grid(14)));
+    }
+
+    #[test]
+    fn keeps_the_future_when_replaying_it() {
+        let mut timeline = played(7);
+        timeline.record(3, &grid(3));
+        assert_eq!(timeline.range(), Some((0, 7)));
+        assert_eq!(timeline.grid_at(6), Some(grid(6)));
+    }
+
+    #[test]
+    fn edits_rewrite_their_generation() {
+        let mut timeline = played(7);
+        timeline.record_edit(5, &[5, 9]);
+        let mut edited = grid(5);
+        edited.set(5, false);
+        edited.set(9, true);
+        assert_eq!(timeline.range(), Some((0, 5)));
+        assert_eq!(timeline.grid_at(5), Some(edited));
+        assert_eq!(timeline.grid_at(4), Some(grid(4)));
+    }
+
+    #[test]
+    fn forgets_the_oldest_generations_past_capacity() {
+        let mut timeline = Timeline::new(3, 40);
+        timeline.reset(0, &grid(0));
+        for generation in 1..=12 {
+            timeline.record(generation, &grid(generation as usize));
+        }
+        let (first, last) = timeline.range().unwrap();
+        assert!(first > 0);
+        assert_eq!(last, 12);
+        assert_eq!(timeline.grid_at(first - 1), None);
+        assert_eq!(timeline.grid_at(12), Some(grid(12)));
+    }
+}
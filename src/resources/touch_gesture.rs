@@ -0,0 +1,37 @@
+use cgmath::Point2;
+
+use super::MouseButtons;
+
+/// What the fingers on the screen are doing, worked out over several frames.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    #[default]
+    Idle,
+    /// one finger that has neither moved far nor been held long yet
+    Pending { start: Point2<f32>, held_for: f32 },
+    /// a finger lifted while still pending, pressing the left button for one run
+    Tap,
+    /// one finger held still past a long press, which opens the menu when lifted
+    Held { start: Point2<f32> },
+    /// one finger dragging as the left button, or as the right one after a long press
+    Drag { erase: bool },
+    /// two or more fingers, moving the camera as the middle button and the wheel do
+    Pan { fingers: usize, distance: f32 },
+}
+
+/// Touch gestures, played back to the other systems as mouse input.
+#[derive(Default)]
+pub struct TouchGesture {
+    pub gesture: Gesture,
+}
+
+impl Gesture {
+    pub fn buttons(&self) -> MouseButtons {
+        match self {
+            Gesture::Idle | Gesture::Pending { .. } | Gesture::Held { .. } => MouseButtons::empty(),
+            Gesture::Tap | Gesture::Drag { erase: false } => MouseButtons::LEFT,
+            Gesture::Drag { erase: true } => MouseButtons::RIGHT,
+            Gesture::Pan { .. } => MouseButtons::MIDDLE,
+        }
+    }
+}
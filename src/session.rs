@@ -0,0 +1,274 @@
+use anyhow::*;
+
+use crate::pattern::{encode_rle, parse_rle, Boundary, Pattern, Rule};
+use crate::resources::{Speed, Tool};
+
+/// Bump when the meaning of an existing key changes.
+/// New keys can be added without a bump: unknown keys are skipped and missing ones keep their defaults.
+/// Version 2 added the paused, stepping and edit phases; version 1 only knew ready and play.
+pub const SESSION_VERSION: u32 = 2;
+const SESSION_HEADER: &str = "#life-session";
+/// Builds before the simulation clock saved a stopped game as a tick of 99999.9 seconds.
+const STOPPED_TICK: f32 = 9999.0;
+
+/// What the game was doing when it was saved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SessionPhase {
+    #[default]
+    Ready,
+    Paused,
+    Playing,
+    /// this many generations were still to run
+    Stepping(u64),
+    Edit(Tool),
+}
+
+impl SessionPhase {
+    fn encode(&self) -> String {
+        match self {
+            SessionPhase::Ready => "ready".to_string(),
+            SessionPhase::Paused => "paused".to_string(),
+            SessionPhase::Playing => "play".to_string(),
+            SessionPhase::Stepping(generations) => format!("step {}", generations),
+            SessionPhase::Edit(tool) => format!("edit {}", tool.name()),
+        }
+    }
+
+    fn decode(value: &str) -> Result<SessionPhase> {
+        let (name, argument) = value.split_once(' ').unwrap_or((value, ""));
+        Ok(match name {
+            "ready" => SessionPhase::Ready,
+            "paused" => SessionPhase::Paused,
+            "play" => SessionPhase::Playing,
+            "step" => SessionPhase::Stepping(argument.parse()?),
+            "edit" => SessionPhase::Edit(argument.parse()?),
+            _ => bail!("unknown phase {}", value),
+        })
+    }
+}
+
+/// Everything needed to pick up where the user left off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub rule: Rule,
+    pub boundary: Boundary,
+    pub generation: u64,
+    pub speed: Speed,
+    pub eye: [f32; 3],
+    pub target: [f32; 3],
+    pub phase: SessionPhase,
+    /// live cells in pattern coordinates, relative to the grid center
+    pub pattern: Pattern,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session {
+            rule: Rule::default(),
+            boundary: Boundary::default(),
+            generation: 0,
+            speed: Speed::PerSecond(crate::configs::GENERATIONS_PER_SECOND),
+            eye: [0.0, 0.0, 60.0],
+            target: [0.0, 0.0, 0.0],
+            phase: SessionPhase::Ready,
+            pattern: Pattern::default(),
+        }
+    }
+}
+
+impl Session {
+    /// One `key value` pair per line, with the cells last as rle so the file stays readable.
+    pub fn encode(&self) -> String {
+        let vector = |v: [f32; 3]| format!("{} {} {}", v[0], v[1], v[2]);
+        let origin = self
+            .pattern
+            .bounding_box()
+            .map(|bbox| [bbox[0], bbox[1]])
+            .unwrap_or_default();
+
+        let mut text = format!("{} {}\n", SESSION_HEADER, SESSION_VERSION);
+        text += &format!("rule {}\n", self.rule);
+        text += &format!("boundary {}\n", self.boundary.name());
+        text += &format!("generation {}\n", self.generation);
+        // tick 은 세대당 초, 예전 파일과 같은 뜻으로 남겨 둔다
+        text += &match self.speed {
+            Speed::PerSecond(rate) => format!("tick {}\n", 1.0 / rate),
+            Speed::PerFrame(generations) => format!("per_frame {}\n", generations),
+        };
+        text += &format!("eye {}\n", vector(self.eye));
+        text += &format!("target {}\n", vector(self.target));
+        text += &format!("phase {}\n", self.phase.encode());
+        text += &format!("origin {} {}\n", origin[0], origin[1]);
+        text += "cells\n";
+        text += &encode_rle(&self.pattern);
+        text
+    }
+
+    pub fn decode(text: &str) -> Result<Session> {
+        let mut lines = text.lines();
+        let header = lines.next().unwrap_or_default();
+        let Some(version) = header.strip_prefix(SESSION_HEADER) else {
+            bail!("not a session file");
+        };
+        let version: u32 = version.trim().parse()?;
+        if version > SESSION_VERSION {
+            bail!(
+                "session version {} is newer than this build supports",
+                version
+            );
+        }
+
+        let vector = |value: &str| -> Result<[f32; 3]> {
+            let parts = value
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<std::result::Result<Vec<f32>, _>>()?;
+            match parts[..] {
+                [x, y, z] => Ok([x, y, z]),
+                _ => bail!("expected three numbers, got {}", value),
+            }
+        };
+
+        let mut session = Session::default();
+        let mut origin = [0, 0];
+        let mut stopped = false;
+        for line in lines.by_ref() {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "rule" => session.rule = value.parse()?,
+                "boundary" => session.boundary = value.parse()?,
+                "generation" => session.generation = value.parse()?,
+                "tick" => {
+                    let tick: f32 = value.parse()?;
+                    stopped = tick > STOPPED_TICK;
+                    session.speed = if stopped {
+                        Session::default().speed
+                    } else {
+                        Speed::PerSecond(1.0 / tick).clamped()
+                    };
+                }
+                "per_frame" => session.speed = Speed::PerFrame(value.parse()?).clamped(),
+                "eye" => session.eye = vector(value)?,
+                "target" => session.target = vector(value)?,
+                "phase" => session.phase = SessionPhase::decode(value)?,
+                "origin" => {
+                    let Some((x, y)) = value.split_once(' ') else {
+                        bail!("origin should be two numbers");
+                    };
+                    origin = [x.parse()?, y.parse()?];
+                }
+                "cells" => break,
+                _ => log::warn!("skipping unknown session key {}", key),
+            }
+        }
+
+        // 멈춰 있던 게임은 재생 중으로 저장됐어도 멈춘 채로 연다
+        if stopped && session.phase == SessionPhase::Playing {
+            session.phase = SessionPhase::Ready;
+        }
+
+        let rle = lines.collect::<Vec<_>>().join("\n");
+        session.pattern = parse_rle(&rle)?.translated(origin[0], origin[1]);
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs;
+
+    const VERSION_1: &str = "#life-session 1
+rule B36/S23
+boundary torus
+generation 42
+tick 0.5
+eye 1 2 30
+target 1 2 0
+phase play
+origin -1 -2
+cells
+x = 3, y = 2
+3o$bo!
+";
+
+    #[test]
+    fn decodes_version_1() {
+        let session = Session::decode(VERSION_1).unwrap();
+        assert_eq!(session.rule.to_string(), "B36/S23");
+        assert_eq!(session.boundary, Boundary::Torus);
+        assert_eq!(session.generation, 42);
+        assert_eq!(session.speed, Speed::PerSecond(2.0));
+        assert_eq!(session.eye, [1.0, 2.0, 30.0]);
+        assert_eq!(session.target, [1.0, 2.0, 0.0]);
+        assert_eq!(session.phase, SessionPhase::Playing);
+        assert_eq!(
+            session.pattern,
+            Pattern::new([[-1, -2], [0, -2], [1, -2], [0, -1]])
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut session = Session::decode(VERSION_1).unwrap();
+        assert_eq!(Session::decode(&session.encode()).unwrap(), session);
+        session.speed = Speed::PerFrame(7);
+        session.phase = SessionPhase::Ready;
+        assert_eq!(Session::decode(&session.encode()).unwrap(), session);
+    }
+
+    #[test]
+    fn round_trips_every_phase() {
+        let mut session = Session::decode(VERSION_1).unwrap();
+        for phase in [
+            SessionPhase::Ready,
+            SessionPhase::Paused,
+            SessionPhase::Playing,
+            SessionPhase::Stepping(12),
+            SessionPhase::Edit(Tool::Pencil),
+            SessionPhase::Edit(Tool::FilledEllipse),
+        ] {
+            session.phase = phase;
+            let text = session.encode();
+            assert!(text.starts_with("#life-session 2\n"));
+            assert_eq!(Session::decode(&text).unwrap().phase, phase);
+        }
+        let text = VERSION_1.replace("phase play", "phase edit spray");
+        assert!(Session::decode(&text).is_err());
+    }
+
+    #[test]
+    fn opens_old_stopped_saves_paused_at_the_default_speed() {
+        let text = VERSION_1.replace("tick 0.5", "tick 99999.9");
+        let session = Session::decode(&text).unwrap();
+        assert_eq!(session.speed, Session::default().speed);
+        assert_eq!(session.phase, SessionPhase::Ready);
+    }
+
+    #[test]
+    fn clamps_speeds() {
+        let text = VERSION_1.replace("tick 0.5", "tick 0.000001");
+        assert_eq!(
+            Session::decode(&text).unwrap().speed,
+            Speed::PerSecond(configs::MAX_GENERATIONS_PER_SECOND)
+        );
+        let text = VERSION_1.replace("tick 0.5", "tick 100");
+        assert_eq!(
+            Session::decode(&text).unwrap().speed,
+            Speed::PerSecond(configs::MIN_GENERATIONS_PER_SECOND)
+        );
+        let text = VERSION_1.replace("tick 0.5", "per_frame 0");
+        assert_eq!(Session::decode(&text).unwrap().speed, Speed::PerFrame(1));
+        let text = VERSION_1.replace("tick 0.5", "per_frame 4000000000");
+        assert_eq!(
+            Session::decode(&text).unwrap().speed,
+            Speed::PerFrame(configs::MAX_STEPS_PER_FRAME)
+        );
+    }
+
+    #[test]
+    fn rejects_other_files_and_newer_versions() {
+        assert!(Session::decode("x = 3, y = 1\n3o!").is_err());
+        assert!(Session::decode(&VERSION_1.replace("session 1", "session 3")).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use specs::{Read, System, Write};
+
+use crate::resources::{AutoStop, Cycle, CycleDetector, Stepped, StopReason};
+
+pub struct CheckAutoStop;
+
+impl<'a> System<'a> for CheckAutoStop {
+    type SystemData = (
+        Read<'a, Stepped>,
+        Read<'a, CycleDetector>,
+        Write<'a, AutoStop>,
+    );
+
+    fn run(&mut self, (stepped, detector, mut auto_stop): Self::SystemData) {
+        for (generation, grid) in &stepped.generations {
+            let generation = *generation;
+            if auto_stop.on_extinction && !grid.cells().contains(&true) {
+                auto_stop.fired = Some((generation, StopReason::Extinct));
+                return;
+            }
+
+            // 주기는 처음 발견된 세대에만 멈춰서, 다시 재생하면 계속 돌아간다
+            if let (Some(max_period), Some((since, cycle))) =
+                (auto_stop.max_period, detector.cycle())
+            {
+                let period = match cycle {
+                    Cycle::StillLife => Some(1),
+                    Cycle::Oscillator { period } => Some(period),
+                    Cycle::Moving { .. } => None,
+                };
+                if since == generation && period.map_or(false, |period| period <= max_period) {
+                    auto_stop.fired = Some((generation, StopReason::Periodic(cycle)));
+                    return;
+                }
+            }
+
+            // 한꺼번에 여러 세대를 건너뛰어도 한도를 넘기지 않는다
+            if auto_stop
+                .generation_limit
+                .map_or(false, |limit| generation >= limit)
+            {
+                auto_stop.fired = Some((generation, StopReason::GenerationLimit));
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::{parse_rle, Grid};
+    use specs::{RunNow, World, WorldExt};
+
+    fn grid_of(rle: &str) -> Grid {
+        let mut grid = Grid::new(16, 16);
+        for [x, y] in parse_rle(rle).unwrap().cells() {
+            grid.set(((x + 8) * 16 + 8 - y) as usize, true);
+        }
+        grid
+    }
+
+    /// Steps `grid` through `generations` as `UpdateCells` and `DetectCycles` would, then checks.
+    fn check(
+        auto_stop: AutoStop,
+        mut grid: Grid,
+        generations: std::ops::Range<u64>,
+    ) -> Option<(u64, StopReason)> {
+        let mut world = World::new();
+        let mut detector = CycleDetector::default();
+        let mut stepped = Stepped::default();
+        for generation in 0..generations.end {
+            detector.observe(generation, &grid);
+            if generations.contains(&generation) {
+                stepped.generations.push((generation, grid.clone()));
+            }
+            grid = grid.step();
+        }
+        world.insert(detector);
+        world.insert(stepped);
+        world.insert(auto_stop);
+        CheckAutoStop.run_now(&world);
+        let fired = world.read_resource::<AutoStop>().fired;
+        fired
+    }
+
+    #[test]
+    fn stops_on_extinction_only_when_asked() {
+        let on = AutoStop {
+            on_extinction: true,
+            ..Default::default()
+        };
+        // 혼자 있는 칸은 다음 세대에 죽는다
+        assert_eq!(
+            check(on, grid_of("o!"), 0..3),
+            Some((1, StopReason::Extinct))
+        );
+        assert_eq!(check(AutoStop::default(), grid_of("o!"), 0..3), None);
+    }
+
+    #[test]
+    fn stops_when_the_period_is_short_enough() {
+        let blinker = StopReason::Periodic(Cycle::Oscillator { period: 2 });
+        let up_to = |max_period| AutoStop {
+            max_period: Some(max_period),
+            ..Default::default()
+        };
+        assert_eq!(check(up_to(2), grid_of("3o!"), 1..3), Some((2, blinker)));
+        assert_eq!(check(up_to(1), grid_of("3o!"), 1..3), None);
+        // 처음 발견한 세대가 지나면 다시 멈추지 않는다
+        assert_eq!(check(up_to(2), grid_of("3o!"), 3..5), None);
+        // 움직이는 패턴은 주기로 치지 않는다
+        assert_eq!(check(up_to(8), grid_of("bo$2bo$3o!"), 1..9), None);
+    }
+
+    #[test]
+    fn stops_at_or_past_the_generation_limit() {
+        let limit = || AutoStop {
+            generation_limit: Some(10),
+            ..Default::default()
+        };
+        let block = grid_of("2o$2o!");
+        assert_eq!(check(limit(), block.clone(), 5..9), None);
+        assert_eq!(
+            check(limit(), block.clone(), 8..12),
+            Some((10, StopReason::GenerationLimit))
+        );
+        // 한 프레임에 한도를 건너뛰어도 멈춘다
+        assert_eq!(
+            check(limit(), block, 12..14),
+            Some((12, StopReason::GenerationLimit))
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use specs::{Read, System, Write};
+
+use crate::resources::{CycleDetector, Stepped};
+
+pub struct DetectCycles;
+
+impl<'a> System<'a> for DetectCycles {
+    type SystemData = (Read<'a, Stepped>, Write<'a, CycleDetector>);
+
+    fn run(&mut self, (stepped, mut detector): Self::SystemData) {
+        for (generation, grid) in &stepped.generations {
+            if let Some(cycle) = detector.observe(*generation, grid) {
+                log::info!("generation {} : {}", generation, cycle);
+            }
+        }
+    }
+}
@@ -20,9 +20,36 @@ pub trait UnifiedDispatcher {
 }
 
 construct_dispatcher!(
-    (UpdateCells, "update_cell", &[]),
-    (UpdateCamera, "update_camera", &[]),
-    (ResetInputDelta, "reset_input", &["update_camera"])
+    (InterpretTouches, "interpret_touches", &[]),
+    (SelectCells, "select_cells", &["interpret_touches"]),
+    (DrawShapes, "draw_shapes", &["select_cells"]),
+    (PaintCells, "paint_cells", &["select_cells"]),
+    (UpdateCells, "update_cell", &["paint_cells"]),
+    (DetectCycles, "detect_cycles", &["update_cell"]),
+    (CheckAutoStop, "auto_stop", &["detect_cycles"]),
+    (RecordStatistics, "record_statistics", &["update_cell"]),
+    (RecordHistory, "record_history", &["update_cell"]),
+    (RecordTimeline, "record_timeline", &["update_cell"]),
+    (UpdateCamera, "update_camera", &["interpret_touches"]),
+    (KeyboardCamera, "keyboard_camera", &["update_camera"]),
+    (
+        MoveCursor,
+        "move_cursor",
+        &["keyboard_camera", "draw_shapes"]
+    ),
+    (
+        ResetInputDelta,
+        "reset_input",
+        &[
+            "interpret_touches",
+            "update_camera",
+            "keyboard_camera",
+            "move_cursor",
+            "paint_cells",
+            "draw_shapes",
+            "select_cells"
+        ]
+    )
 );
 
 pub fn new() -> Box<dyn UnifiedDispatcher + 'static> {
@@ -0,0 +1,49 @@
+use specs::{Read, System, Write};
+
+use crate::resources::{Camera, DrawTool, Drawn, InputHandler, MouseButtons, Selection, Tool};
+
+/// Drags with a shape tool in the edit phase. The shape is drawn once the button is released.
+pub struct DrawShapes;
+
+impl<'a> System<'a> for DrawShapes {
+    type SystemData = (
+        Read<'a, InputHandler>,
+        Read<'a, Camera>,
+        Read<'a, Selection>,
+        Write<'a, DrawTool>,
+    );
+
+    fn run(&mut self, (input_handler, camera, selection, mut draw_tool): Self::SystemData) {
+        // 연필은 PaintCells 가 그린다
+        if !draw_tool.editing || draw_tool.tool == Tool::Pencil || selection.enabled {
+            draw_tool.anchor = None;
+            return;
+        }
+
+        let pressed = if input_handler.is_button_pressed(MouseButtons::LEFT) {
+            Some(true)
+        } else if input_handler.is_button_pressed(MouseButtons::RIGHT) {
+            Some(false)
+        } else {
+            None
+        };
+        let cell = camera.screen_to_cell(input_handler.mouse_position());
+        match (draw_tool.anchor, pressed) {
+            (None, Some(alive)) => {
+                draw_tool.anchor = Some((cell, alive));
+                draw_tool.cursor = cell;
+            }
+            (Some(_), Some(_)) => draw_tool.cursor = cell,
+            (Some((from, alive)), None) => {
+                draw_tool.drawn = Some(Drawn {
+                    tool: draw_tool.tool,
+                    from,
+                    to: draw_tool.cursor,
+                    alive,
+                });
+                draw_tool.anchor = None;
+            }
+            (None, None) => {}
+        }
+    }
+}
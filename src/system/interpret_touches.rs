@@ -0,0 +1,116 @@
+use cgmath::{EuclideanSpace, MetricSpace, Point2, Vector2};
+use specs::{Read, System, Write};
+
+use crate::configs;
+use crate::resources::{Camera, ContextMenu, FrameTime, Gesture, InputHandler, TouchGesture};
+
+/// Turns fingers into mouse input for the systems after it.
+/// One finger draws, two pan and pinch to zoom. A long press opens the context menu
+/// when lifted, or erases like the right button when dragged on.
+pub struct InterpretTouches;
+
+impl<'a> System<'a> for InterpretTouches {
+    type SystemData = (
+        Write<'a, TouchGesture>,
+        Write<'a, InputHandler>,
+        Write<'a, ContextMenu>,
+        Read<'a, Camera>,
+        Read<'a, FrameTime>,
+    );
+
+    fn run(
+        &mut self,
+        (mut touch, mut input_handler, mut menu, camera, frame_time): Self::SystemData,
+    ) {
+        let points = input_handler.touches().collect::<Vec<_>>();
+        let gesture = match (touch.gesture, points.len()) {
+            // 메뉴가 떠 있으면 탭은 항목을 고르는 데만 쓴다
+            (Gesture::Pending { start, .. }, 0) if menu.cell.is_some() => {
+                menu.tap(camera.screen_to_ndc(start));
+                Gesture::Idle
+            }
+            // 드래그가 되기 전에 떼면 한 칸만 찍는다
+            (Gesture::Pending { .. }, 0) => Gesture::Tap,
+            (Gesture::Held { start }, 0) => {
+                let [x, y] = camera.screen_to_cell(start);
+                let corner = camera.world_to_ndc([x as f32 + 0.5, y as f32 + 0.5, 0.0]);
+                menu.open([x, y], corner);
+                Gesture::Idle
+            }
+            (_, 0) => Gesture::Idle,
+            (Gesture::Idle | Gesture::Tap, 1) => {
+                input_handler.warp_cursor(points[0]);
+                Gesture::Pending {
+                    start: points[0],
+                    held_for: 0.0,
+                }
+            }
+            (Gesture::Pending { start, held_for }, 1) => {
+                let held_for = held_for + frame_time.dt;
+                if start.distance(points[0]) > configs::TOUCH_SLOP {
+                    // 획은 손가락이 처음 닿은 칸부터 이어진다
+                    input_handler.warp_cursor(start);
+                    Gesture::Drag { erase: false }
+                } else if held_for >= configs::LONG_PRESS_SECONDS {
+                    Gesture::Held { start }
+                } else {
+                    input_handler.warp_cursor(points[0]);
+                    Gesture::Pending { start, held_for }
+                }
+            }
+            (Gesture::Held { start }, 1) => {
+                if start.distance(points[0]) > configs::TOUCH_SLOP {
+                    input_handler.warp_cursor(start);
+                    Gesture::Drag { erase: true }
+                } else {
+                    Gesture::Held { start }
+                }
+            }
+            (Gesture::Drag { erase }, 1) => {
+                input_handler.cursor_moved(points[0]);
+                Gesture::Drag { erase }
+            }
+            // 한 번 두 손가락이 되면 모두 뗄 때까지 그리지 않는다
+            (gesture, fingers) => {
+                let centroid = Point2::from_vec(
+                    points
+                        .iter()
+                        .map(|point| point.to_vec())
+                        .sum::<Vector2<f32>>()
+                        / fingers as f32,
+                );
+                let distance = points
+                    .iter()
+                    .map(|point| point.distance(centroid))
+                    .sum::<f32>()
+                    / fingers as f32;
+                match gesture {
+                    Gesture::Pan {
+                        fingers: before,
+                        distance: before_distance,
+                    } if before == fingers => {
+                        input_handler.cursor_moved(centroid);
+                        if distance != before_distance {
+                            // 벌리면 가까워지도록 휠과 같은 방향으로 보낸다
+                            input_handler.mouse_wheel(Vector2::new(
+                                0.0,
+                                (before_distance - distance) * configs::PINCH_ZOOM_SPEED,
+                            ));
+                        }
+                    }
+                    // 손가락 수가 바뀌면 중심이 튀므로 움직인 것으로 치지 않는다
+                    _ => input_handler.warp_cursor(centroid),
+                }
+                Gesture::Pan { fingers, distance }
+            }
+        };
+
+        if !matches!(gesture, Gesture::Idle | Gesture::Pending { .. }) {
+            // 메뉴를 고르지 않고 다른 동작을 하면 닫는다
+            menu.cell = None;
+        }
+
+        input_handler.touch_buttons(gesture.buttons());
+        touch.gesture = gesture;
+    }
+}
@@ -0,0 +1,47 @@
+use specs::{Read, System, Write};
+
+use crate::configs;
+use crate::resources::{Action, Camera, FrameTime, InputHandler, KeyMap, KeyboardPan};
+
+/// Pans with the pan keys, faster the longer they are held, and zooms with the zoom keys.
+pub struct KeyboardCamera;
+
+impl<'a> System<'a> for KeyboardCamera {
+    type SystemData = (
+        Write<'a, Camera>,
+        Write<'a, KeyboardPan>,
+        Read<'a, InputHandler>,
+        Read<'a, KeyMap>,
+        Read<'a, FrameTime>,
+    );
+
+    fn run(&mut self, (mut camera, mut pan, input_handler, key_map, frame_time): Self::SystemData) {
+        let held = |action| key_map.is_held(action, &input_handler) as i32 as f32;
+        // 마우스 끌기와 같은 단위라서 화면 위쪽이 y 음수다
+        let direction = [
+            held(Action::PanRight) - held(Action::PanLeft),
+            held(Action::PanDown) - held(Action::PanUp),
+        ];
+        let zoom = held(Action::ZoomOut) - held(Action::ZoomIn);
+
+        if direction == [0.0, 0.0] {
+            pan.held_for = 0.0;
+        } else {
+            pan.held_for += frame_time.dt;
+        }
+        let speed = (configs::KEY_PAN_SPEED * (1.0 + pan.held_for * configs::KEY_PAN_ACCELERATION))
+            .min(configs::KEY_PAN_MAX_SPEED);
+        let length = (direction[0] * direction[0] + direction[1] * direction[1])
+            .sqrt()
+            .max(1.0);
+        let step = speed * frame_time.dt / length;
+        let delta = [
+            direction[0] * step,
+            direction[1] * step,
+            zoom * configs::KEY_ZOOM_SPEED * frame_time.dt,
+        ];
+        if delta != [0.0; 3] {
+            camera.move_by(delta);
+        }
+    }
+}
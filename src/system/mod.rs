@@ -1,10 +1,32 @@
+pub use check_auto_stop::CheckAutoStop;
+pub use detect_cycles::DetectCycles;
 pub use dispatcher::UnifiedDispatcher;
+pub use draw_shapes::DrawShapes;
+pub use interpret_touches::InterpretTouches;
+pub use keyboard_camera::KeyboardCamera;
+pub use move_cursor::MoveCursor;
+pub use paint_cells::PaintCells;
+pub use record_history::RecordHistory;
+pub use record_statistics::RecordStatistics;
+pub use record_timeline::RecordTimeline;
 pub use reset_input::ResetInputDelta;
+pub use select_cells::SelectCells;
 pub use update_camera::UpdateCamera;
 pub use update_cells::UpdateCells;
 
+mod check_auto_stop;
+mod detect_cycles;
 mod dispatcher;
+mod draw_shapes;
+mod interpret_touches;
+mod keyboard_camera;
+mod move_cursor;
+mod paint_cells;
+mod record_history;
+mod record_statistics;
+mod record_timeline;
 mod reset_input;
+mod select_cells;
 
 mod update_camera;
 mod update_cells;
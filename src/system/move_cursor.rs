@@ -0,0 +1,89 @@
+use specs::{Join, Read, ReadStorage, System, Write};
+
+use crate::components::Cell;
+use crate::configs;
+use crate::entity_builder::cell_index;
+use crate::resources::{
+    Action, Camera, CellCursor, DrawTool, Drawn, FrameTime, InputHandler, KeyMap, Tool,
+};
+
+/// Moves the keyboard cell cursor and toggles the cell under it.
+/// The toggle goes to `GameState` as a one cell pencil stroke, like the shape tools.
+pub struct MoveCursor;
+
+impl<'a> System<'a> for MoveCursor {
+    type SystemData = (
+        Write<'a, CellCursor>,
+        Write<'a, DrawTool>,
+        ReadStorage<'a, Cell>,
+        Read<'a, InputHandler>,
+        Read<'a, KeyMap>,
+        Read<'a, Camera>,
+        Read<'a, FrameTime>,
+    );
+
+    fn run(
+        &mut self,
+        (mut cursor, mut draw_tool, cells, input_handler, key_map, camera, frame_time): Self::SystemData,
+    ) {
+        // 마우스를 움직이면 마우스로 돌아간 것으로 본다
+        if input_handler.mouse_delta != cgmath::Vector2::new(0.0, 0.0) {
+            cursor.cell = None;
+        }
+
+        let held = |action| key_map.is_held(action, &input_handler) as i32;
+        let direction = [
+            held(Action::CursorRight) - held(Action::CursorLeft),
+            held(Action::CursorUp) - held(Action::CursorDown),
+        ];
+        let toggle = key_map.is_pressed(Action::ToggleCell, &input_handler);
+        if cursor.cell.is_none() && (direction != [0, 0] || toggle) {
+            // 처음 누르면 화면 가운데에 나타나기만 한다
+            let center = camera.screen_size().map(|size| size / 2.0);
+            let [x, y] = camera.screen_to_cell(center.into());
+            let [half_x, half_y] = configs::GRID_SIZE_HALF.map(|half| half as i32);
+            cursor.cell = Some([x.clamp(-half_x, half_x), y.clamp(-half_y, half_y)]);
+            cursor.held_for = 0.0;
+            cursor.moves = 1;
+            return;
+        }
+        let Some(mut cell) = cursor.cell else {
+            return;
+        };
+
+        if direction == [0, 0] {
+            cursor.held_for = 0.0;
+            cursor.moves = 0;
+        } else {
+            cursor.held_for += frame_time.dt;
+            // 한 번 누르면 한 칸, 계속 누르고 있으면 잠시 뒤부터 일정한 속도로 움직인다
+            let repeated = ((cursor.held_for - configs::CURSOR_REPEAT_DELAY).max(0.0)
+                * configs::CURSOR_REPEAT_RATE) as u32;
+            let moves = 1 + repeated;
+            for _ in cursor.moves..moves {
+                let next = [cell[0] + direction[0], cell[1] + direction[1]];
+                if cell_index(next).is_some() {
+                    cell = next;
+                }
+            }
+            cursor.moves = cursor.moves.max(moves);
+            cursor.cell = Some(cell);
+        }
+
+        if toggle {
+            let Some(index) = cell_index(cell) else {
+                return;
+            };
+            let alive = cells
+                .join()
+                .find(|candidate| candidate.index == index)
+                .map_or(false, |candidate| candidate.alive);
+            draw_tool.drawn = Some(Drawn {
+                tool: Tool::Pencil,
+                from: cell,
+                to: cell,
+                alive: !alive,
+            });
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashSet;
+
+use specs::{Read, System, Write, WriteStorage};
+
+use crate::components::Cell;
+use crate::entity_builder::{apply_cell_edit, cell_index};
+use crate::pattern::line;
+use crate::resources::{
+    Camera, CycleDetector, DrawTool, History, InputHandler, Mirror, MouseButtons, Selection,
+    SimulationClock, Statistics, Stroke, Timeline, Tool,
+};
+
+/// Left drag paints cells alive, right drag erases them.
+pub struct PaintCells;
+
+impl<'a> System<'a> for PaintCells {
+    type SystemData = (
+        WriteStorage<'a, Cell>,
+        Read<'a, InputHandler>,
+        Read<'a, Camera>,
+        Write<'a, Stroke>,
+        Write<'a, CycleDetector>,
+        Read<'a, Selection>,
+        Write<'a, History>,
+        Read<'a, SimulationClock>,
+        Write<'a, Timeline>,
+        Read<'a, DrawTool>,
+        Read<'a, Mirror>,
+        Write<'a, Statistics>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            mut cells,
+            input_handler,
+            camera,
+            mut stroke,
+            mut detector,
+            selection,
+            mut history,
+            clock,
+            mut timeline,
+            draw_tool,
+            mirror,
+            mut statistics,
+        ): Self::SystemData,
+    ) {
+        let alive = if selection.enabled || (draw_tool.editing && draw_tool.tool != Tool::Pencil) {
+            None
+        } else if input_handler.is_button_pressed(MouseButtons::LEFT) {
+            Some(true)
+        } else if input_handler.is_button_pressed(MouseButtons::RIGHT) {
+            Some(false)
+        } else {
+            None
+        };
+        let Some(alive) = alive else {
+            // 버튼을 떼면 한 획이 끝나서 되돌리기 한 번에 묶인다
+            if stroke.last_cell.take().is_some() {
+                history.close_stroke();
+            }
+            return;
+        };
+
+        let cell = camera.screen_to_cell(input_handler.mouse_position());
+        if stroke.last_cell == Some(cell) {
+            return;
+        }
+        let from = stroke.last_cell.unwrap_or(cell);
+        stroke.last_cell = Some(cell);
+
+        let targets = mirror
+            .mirrored(&line(from, cell))
+            .into_iter()
+            .filter_map(cell_index)
+            .collect::<HashSet<_>>();
+        apply_cell_edit(
+            &mut cells,
+            &targets,
+            |_| alive,
+            (&mut detector, &mut timeline, &mut history, &mut statistics),
+            clock.generation,
+            true,
+        );
+    }
+}
@@ -0,0 +1,15 @@
+use specs::{Read, System, Write};
+
+use crate::resources::{History, Stepped};
+
+pub struct RecordHistory;
+
+impl<'a> System<'a> for RecordHistory {
+    type SystemData = (Read<'a, Stepped>, Write<'a, History>);
+
+    fn run(&mut self, (stepped, mut history): Self::SystemData) {
+        for (generation, grid) in &stepped.generations {
+            history.record_step(*generation, grid);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use specs::{Read, System, Write};
+
+use crate::resources::{Statistics, Stepped};
+
+pub struct RecordStatistics;
+
+impl<'a> System<'a> for RecordStatistics {
+    type SystemData = (Read<'a, Stepped>, Write<'a, Statistics>);
+
+    fn run(&mut self, (stepped, mut statistics): Self::SystemData) {
+        for (generation, grid) in &stepped.generations {
+            statistics.record(*generation, grid);
+        }
+    }
+}
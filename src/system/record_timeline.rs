@@ -0,0 +1,15 @@
+use specs::{Read, System, Write};
+
+use crate::resources::{Stepped, Timeline};
+
+pub struct RecordTimeline;
+
+impl<'a> System<'a> for RecordTimeline {
+    type SystemData = (Read<'a, Stepped>, Write<'a, Timeline>);
+
+    fn run(&mut self, (stepped, mut timeline): Self::SystemData) {
+        for (generation, grid) in &stepped.generations {
+            timeline.record(*generation, grid);
+        }
+    }
+}
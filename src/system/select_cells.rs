@@ -0,0 +1,36 @@
+use specs::{Read, System, Write};
+
+use crate::resources::{Camera, CycleDetector, InputHandler, MouseButtons, Selection};
+
+/// Left drag with the select tool on spans a rectangle from where the drag started.
+pub struct SelectCells;
+
+impl<'a> System<'a> for SelectCells {
+    type SystemData = (
+        Read<'a, InputHandler>,
+        Read<'a, Camera>,
+        Write<'a, Selection>,
+        Write<'a, CycleDetector>,
+    );
+
+    fn run(&mut self, (input_handler, camera, mut selection, mut detector): Self::SystemData) {
+        if !selection.enabled || !input_handler.is_button_pressed(MouseButtons::LEFT) {
+            selection.anchor = None;
+            return;
+        }
+
+        let cell = camera.screen_to_cell(input_handler.mouse_position());
+        let anchor = *selection.anchor.get_or_insert(cell);
+        let rect = Some([
+            anchor[0].min(cell[0]),
+            anchor[1].min(cell[1]),
+            anchor[0].max(cell[0]),
+            anchor[1].max(cell[1]),
+        ]);
+        if selection.rect != rect {
+            selection.rect = rect;
+            // 선택 영역 안에서만 주기를 찾는다
+            detector.set_region(selection.pattern_region());
+        }
+    }
+}